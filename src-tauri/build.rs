@@ -1,3 +1,12 @@
 fn main() {
+    // Engine updates pick the binaries built for this target
+    println!(
+        "cargo:rustc-env=GODSPEED_TARGET_TRIPLE={}",
+        std::env::var("TARGET").expect("TARGET is set by cargo")
+    );
+
+    // Engine updates trust only the release key embedded at build time
+    println!("cargo:rerun-if-env-changed=GODSPEED_ENGINE_SIGNING_PUBLIC_KEY");
+
     tauri_build::build()
 }
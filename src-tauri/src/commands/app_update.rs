@@ -1,58 +1,230 @@
 //! App update commands.
 //!
 //! Provides commands for checking and installing application updates
-//! via GitHub Releases API.
+//! via GitHub Releases API or a static JSON manifest. The release asset is
+//! picked to match the running OS and CPU architecture, and only the asset
+//! offered by the last check can be installed, after its published SHA-256
+//! digest has been verified.
 
-use std::fs::File;
-use std::io::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
 
-use tauri::AppHandle;
+use semver::Version;
+use serde::de::DeserializeOwned;
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_opener::OpenerExt;
 
-use crate::config::{DOWNLOAD_TIMEOUT_SECS, GITHUB_API_URL, UPDATE_MSI_FILENAME, USER_AGENT};
+use crate::commands::settings::{validate_update_url, AppFeedFormat, SettingsState, UpdateSources};
+use crate::config::{DOWNLOAD_TIMEOUT_SECS, MAX_APP_UPDATE_BYTES, USER_AGENT};
 use crate::error::AppError;
+use crate::utils::http::{download_to_file, file_name_from_url};
+use crate::utils::integrity::verify_sha256;
+use crate::utils::zip::make_executable;
+
+/// Installable package formats published with each release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageKind {
+    AppImage,
+    Deb,
+    Rpm,
+    Dmg,
+    Msi,
+    Exe,
+}
+
+impl PackageKind {
+    const ALL: [PackageKind; 6] = [
+        PackageKind::AppImage,
+        PackageKind::Deb,
+        PackageKind::Rpm,
+        PackageKind::Dmg,
+        PackageKind::Msi,
+        PackageKind::Exe,
+    ];
+
+    /// File extension of the package, including the dot.
+    fn extension(self) -> &'static str {
+        match self {
+            PackageKind::AppImage => ".appimage",
+            PackageKind::Deb => ".deb",
+            PackageKind::Rpm => ".rpm",
+            PackageKind::Dmg => ".dmg",
+            PackageKind::Msi => ".msi",
+            PackageKind::Exe => ".exe",
+        }
+    }
+
+    /// Detect the package format from a file name (case-insensitive).
+    fn from_file_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|kind| name.ends_with(kind.extension()))
+    }
+}
 
 /// Response structure for update check results.
 #[derive(serde::Serialize)]
 pub struct UpdateInfo {
     pub update_available: bool,
-    pub latest_version: String,
+    /// Newest release on the selected channel, even if it is not newer than
+    /// the current version; `None` if the feed lists no usable release.
+    pub latest_version: Option<String>,
+    /// Version offered for install; `None` without an update.
+    pub update_version: Option<String>,
+    /// Empty if the release has no package for this platform.
     pub download_url: String,
+    /// Format of the package at `download_url`.
+    pub package: Option<PackageKind>,
+    /// Whether the latest version is a pre-release (beta channel only).
+    pub prerelease: bool,
+    /// Release notes (Markdown).
+    pub release_notes: Option<String>,
+    /// Publication time (RFC 3339).
+    pub published_at: Option<String>,
 }
 
-/// GitHub API response structures.
+/// A release, as returned by the GitHub API or listed in a static manifest.
+///
+/// Manifests may use the shorter `version`, `notes` and `url` field names.
 #[derive(serde::Deserialize)]
-struct GitHubRelease {
+struct Release {
+    #[serde(alias = "version")]
     tag_name: String,
-    assets: Vec<GitHubAsset>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(alias = "notes")]
+    body: Option<String>,
+    published_at: Option<String>,
+    assets: Vec<ReleaseAsset>,
 }
 
+/// A release asset. GitHub publishes its digest as `sha256:<hex>`;
+/// manifests may give the bare hex digest as `sha256`.
 #[derive(serde::Deserialize)]
-struct GitHubAsset {
+struct ReleaseAsset {
     name: String,
+    #[serde(alias = "url")]
     browser_download_url: String,
+    #[serde(default, alias = "sha256")]
+    digest: Option<String>,
+}
+
+impl ReleaseAsset {
+    /// The asset's SHA-256 digest as lowercase hex, if one is published.
+    fn sha256(&self) -> Option<String> {
+        let digest = self.digest.as_deref()?.trim();
+        let hex = digest.strip_prefix("sha256:").unwrap_or(digest);
+
+        (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_ascii_lowercase())
+    }
 }
 
-/// Check for app updates via GitHub Releases API.
+/// Static JSON manifest format for app updates, e.g. an internal mirror:
 ///
-/// Fetches the latest release from GitHub and compares with the current version.
-/// Returns update availability and download URL for the MSI installer.
-#[tauri::command]
-pub async fn check_app_update(current_version: String) -> Result<UpdateInfo, AppError> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| AppError::logic(format!("Failed to create HTTP client: {}", e)))?;
+/// ```json
+/// { "releases": [ { "version": "2.2.0", "notes": "...", "published_at": "2025-01-01T00:00:00Z",
+///   "assets": [ { "name": "Godspeed_2.2.0_x64_en-US.msi", "url": "https://...", "sha256": "..." } ] } ] }
+/// ```
+#[derive(serde::Deserialize)]
+struct ReleaseManifest {
+    releases: Vec<Release>,
+}
+
+/// The update package offered by the last check.
+struct OfferedPackage {
+    url: String,
+    sha256: Option<String>,
+}
+
+/// Tauri-managed state remembering the package offered by `check_app_update`,
+/// so `install_app_update` never downloads from any other URL.
+#[derive(Default)]
+pub struct AppUpdateState(Mutex<Option<OfferedPackage>>);
+
+/// Package formats this platform can install, most preferred first.
+#[cfg(target_os = "windows")]
+fn preferred_packages() -> Vec<PackageKind> {
+    vec![PackageKind::Msi, PackageKind::Exe]
+}
+
+#[cfg(target_os = "macos")]
+fn preferred_packages() -> Vec<PackageKind> {
+    vec![PackageKind::Dmg]
+}
+
+/// Package formats this platform can install, most preferred first.
+///
+/// A running AppImage updates itself; otherwise the distribution's native
+/// package format wins over a standalone AppImage.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn preferred_packages() -> Vec<PackageKind> {
+    if std::env::var_os("APPIMAGE").is_some() {
+        return vec![PackageKind::AppImage];
+    }
+
+    let exists = |path: &str| std::path::Path::new(path).exists();
+
+    if exists("/etc/debian_version") {
+        vec![PackageKind::Deb, PackageKind::AppImage]
+    } else if exists("/etc/redhat-release") || exists("/etc/fedora-release") {
+        vec![PackageKind::Rpm, PackageKind::AppImage]
+    } else {
+        vec![PackageKind::AppImage, PackageKind::Deb, PackageKind::Rpm]
+    }
+}
+
+/// Architecture spellings used in release asset names for this CPU.
+///
+/// `x86_64` and `x86-64` are matched as `x64` (see [`name_matches_arch`]).
+fn arch_aliases() -> &'static [&'static str] {
+    match std::env::consts::ARCH {
+        "x86_64" => &["x64", "amd64", "universal"],
+        "aarch64" => &["aarch64", "arm64", "universal"],
+        "x86" => &["x86", "i686", "i386"],
+        _ => &["universal"],
+    }
+}
+
+/// Whether an asset name contains one of `aliases` as a whole token.
+///
+/// Names are split on `_`, `-`, `.` and spaces, so `x86` does not match
+/// `x64` builds; `x86_64` is first folded into the single token `x64`.
+fn name_matches_arch(name: &str, aliases: &[&str]) -> bool {
+    let name = name
+        .to_lowercase()
+        .replace("x86_64", "x64")
+        .replace("x86-64", "x64");
+
+    name.split(['_', '-', '.', ' ']).any(|token| aliases.contains(&token))
+}
+
+/// Pick the release asset for this OS and architecture.
+fn select_asset(assets: &[ReleaseAsset]) -> Option<&ReleaseAsset> {
+    let aliases = arch_aliases();
+
+    preferred_packages().into_iter().find_map(|kind| {
+        assets.iter().find(|asset| {
+            PackageKind::from_file_name(&asset.name) == Some(kind) && name_matches_arch(&asset.name, aliases)
+        })
+    })
+}
 
+/// Fetch a JSON document from the update feed.
+async fn fetch_json<T: DeserializeOwned>(client: &reqwest::Client, url: &str) -> Result<T, AppError> {
     let response = client
-        .get(GITHUB_API_URL)
+        .get(url)
         .header("User-Agent", USER_AGENT)
         .send()
         .await?;
 
     if !response.status().is_success() {
         return Err(AppError::logic(format!(
-            "GitHub API error: {} - {}",
+            "Update server error: {} - {}",
             response.status(),
             response
                 .status()
@@ -61,87 +233,201 @@ pub async fn check_app_update(current_version: String) -> Result<UpdateInfo, App
         )));
     }
 
-    let release: GitHubRelease = response.json().await?;
+    Ok(response.json().await?)
+}
+
+/// Parse a release tag such as `v2.1.1` or `2.2.0-beta.1` as semver.
+fn parse_version(tag: &str) -> Option<Version> {
+    Version::parse(tag.trim().trim_start_matches('v')).ok()
+}
+
+/// The releases of a feed compared with the running version.
+struct ReleaseChoice {
+    /// Newest release on the selected channel.
+    latest: Option<Version>,
+    /// The release to offer: the newest one, if it is newer than the current version.
+    offer: Option<(Version, Release)>,
+}
+
+/// Pick the newest release and decide whether to offer it over `current`.
+///
+/// Drafts and tags that are not valid semver are ignored, as are
+/// pre-releases unless `beta_updates` is set. Only a release strictly newer
+/// than `current` is offered.
+fn pick_release(releases: Vec<Release>, current: &Version, beta_updates: bool) -> ReleaseChoice {
+    let newest = releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| parse_version(&release.tag_name).map(|version| (version, release)))
+        .filter(|(version, release)| beta_updates || (version.pre.is_empty() && !release.prerelease))
+        .max_by(|(a, _), (b, _)| a.cmp(b));
+
+    ReleaseChoice {
+        latest: newest.as_ref().map(|(version, _)| version.clone()),
+        offer: newest.filter(|(version, _)| version > current),
+    }
+}
+
+/// Fetch the candidate releases from the configured update feed.
+///
+/// From the GitHub API this is the latest stable release, or the full
+/// release list (derived from the `releases/latest` URL) for the beta channel.
+/// A static manifest always lists every release.
+async fn fetch_releases(
+    client: &reqwest::Client,
+    sources: &UpdateSources,
+    beta_updates: bool,
+) -> Result<Vec<Release>, AppError> {
+    match sources.app_feed {
+        AppFeedFormat::GithubReleases if beta_updates => {
+            let list_url = sources
+                .app_url
+                .strip_suffix("/latest")
+                .unwrap_or(&sources.app_url);
+            fetch_json(client, &format!("{}?per_page=30", list_url)).await
+        }
+        AppFeedFormat::GithubReleases => Ok(vec![fetch_json(client, &sources.app_url).await?]),
+        AppFeedFormat::Manifest => {
+            let manifest: ReleaseManifest = fetch_json(client, &sources.app_url).await?;
+            Ok(manifest.releases)
+        }
+    }
+}
+
+/// Check for app updates via the GitHub Releases API or a static manifest,
+/// whichever `update_sources` in the settings points at.
+///
+/// Only a release whose semver is strictly newer than the current version
+/// counts as an update, so downgrades and re-tags never prompt. With
+/// `beta_updates` enabled in the settings, the full release list is
+/// searched (skipping drafts) and pre-releases are offered too; otherwise
+/// only the latest stable release is considered.
+///
+/// Returns update availability, release notes and the download URL for the
+/// package matching this OS and architecture.
+#[tauri::command]
+pub async fn check_app_update(
+    settings: State<'_, SettingsState>,
+    update_state: State<'_, AppUpdateState>,
+    current_version: String,
+) -> Result<UpdateInfo, AppError> {
+    let current = parse_version(&current_version)
+        .ok_or_else(|| AppError::logic(format!("Invalid current version: {}", current_version)))?;
+    *update_state.0.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    let beta_updates = settings.current().beta_updates;
+    let sources = settings.update_sources();
+
+    let client = settings.network().http_client(std::time::Duration::from_secs(30))?;
+
+    let releases = fetch_releases(&client, &sources, beta_updates).await?;
 
-    // Clean version strings for comparison (remove 'v' prefix if present)
-    let latest_clean = release.tag_name.trim_start_matches('v').to_string();
-    let current_clean = current_version.trim_start_matches('v').to_string();
+    let choice = pick_release(releases, &current, beta_updates);
+    let latest_version = choice.latest.as_ref().map(Version::to_string);
+    let Some((update_version, release)) = choice.offer else {
+        return Ok(UpdateInfo {
+            update_available: false,
+            latest_version,
+            update_version: None,
+            download_url: String::new(),
+            package: None,
+            prerelease: false,
+            release_notes: None,
+            published_at: None,
+        });
+    };
 
-    // Find the MSI asset URL
-    let download_url = release
-        .assets
-        .iter()
-        .find(|asset| asset.name.ends_with(".msi"))
+    // Find the package for this platform
+    let asset = select_asset(&release.assets);
+    let download_url = asset
         .map(|asset| asset.browser_download_url.clone())
         .unwrap_or_default();
+    let package = asset.and_then(|asset| PackageKind::from_file_name(&asset.name));
 
-    // Compare versions (simple string comparison works for semver)
-    let update_available = latest_clean != current_clean && !latest_clean.is_empty();
+    if let Some(asset) = asset {
+        validate_update_url("Update package", &download_url)?;
+        *update_state.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(OfferedPackage {
+            url: download_url.clone(),
+            sha256: asset.sha256(),
+        });
+    }
 
     Ok(UpdateInfo {
-        update_available,
-        latest_version: latest_clean,
+        update_available: true,
+        latest_version,
+        update_version: Some(update_version.to_string()),
         download_url,
+        package,
+        prerelease: release.prerelease || !update_version.pre.is_empty(),
+        release_notes: release.body.filter(|body| !body.trim().is_empty()),
+        published_at: release.published_at,
     })
 }
 
-/// Download and install an app update from the given MSI URL.
+/// Download and install the app update offered by the last `check_app_update`.
+///
+/// `url` must be the `download_url` that check returned. The package is
+/// verified against the SHA-256 digest published with the release before
+/// anything is installed; releases without a digest are refused.
 ///
-/// Downloads the MSI installer to a temp directory and opens it with the
-/// system's default handler, allowing Windows to show the installation UI.
-/// Does NOT force app exit - lets the user/installer handle that.
+/// How the package is installed depends on its format:
+/// * AppImage - replaces the running AppImage in place and restarts it
+///   (or is saved to the Downloads folder when not running as an AppImage)
+/// * MSI / EXE / DMG / DEB / RPM - opened with the system's default handler,
+///   which shows the platform's installer UI
+///
+/// Installers do NOT force app exit - the user/installer handles that.
 #[tauri::command]
-pub async fn install_app_update(app: AppHandle, url: String) -> Result<String, AppError> {
+pub async fn install_app_update(
+    app: AppHandle,
+    settings: State<'_, SettingsState>,
+    update_state: State<'_, AppUpdateState>,
+    url: String,
+) -> Result<String, AppError> {
     if url.is_empty() {
         return Err(AppError::logic("No download URL provided."));
     }
 
-    // Step 1: Create temp directory for the download
-    let temp_dir = std::env::temp_dir();
-    let msi_path = temp_dir.join(UPDATE_MSI_FILENAME);
-
-    // Clean up any previous download
-    if msi_path.exists() {
-        let _ = std::fs::remove_file(&msi_path);
-    }
+    let sha256 = {
+        let offered = update_state.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match offered.as_ref() {
+            Some(offered) if offered.url == url => offered.sha256.clone().ok_or_else(|| {
+                AppError::IntegrityCheckFailed(
+                    "The release does not publish a SHA-256 digest for this package; install it manually."
+                        .to_string(),
+                )
+            })?,
+            _ => {
+                return Err(AppError::logic(
+                    "This package was not offered by the last update check. Check for updates again.",
+                ))
+            }
+        }
+    };
 
-    // Step 2: Download the MSI file
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| AppError::logic(format!("Failed to create HTTP client: {}", e)))?;
+    // Step 1: Identify the package. Release assets carry the version in their
+    // name, so a partial file left by an interrupted attempt is only ever
+    // resumed for the same release.
+    let file_name =
+        file_name_from_url(&url).ok_or_else(|| AppError::logic(format!("Invalid update package URL: {}", url)))?;
+    let package = PackageKind::from_file_name(&file_name)
+        .ok_or_else(|| AppError::logic(format!("Unsupported update package: {}", file_name)))?;
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", USER_AGENT)
-        .send()
-        .await?;
+    let client = settings
+        .network()
+        .http_client(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))?;
 
-    if !response.status().is_success() {
-        return Err(AppError::logic(format!(
-            "Download failed: {} - {}",
-            response.status(),
-            response
-                .status()
-                .canonical_reason()
-                .unwrap_or("Unknown error")
-        )));
+    // Step 2: Download and install
+    if package == PackageKind::AppImage {
+        return install_appimage(&app, &client, &url, &file_name, &sha256).await;
     }
 
-    let bytes = response.bytes().await?;
-
-    let mut file =
-        File::create(&msi_path).map_err(|e| AppError::logic(format!("Failed to create MSI file: {}", e)))?;
+    let installer_path = std::env::temp_dir().join(&file_name);
+    download_verified(&app, &client, &url, &installer_path, &sha256).await?;
 
-    file.write_all(&bytes)
-        .map_err(|e| AppError::logic(format!("Failed to write MSI file: {}", e)))?;
-
-    drop(file); // Ensure file handle is released
-
-    // Step 3: Open the MSI file with the system's default handler
-    // This will show the Windows Installer UI to the user
+    // Open the package with the system's default handler
+    // (Windows Installer, the macOS disk image, or the Linux software center)
     app.opener()
-        .open_path(msi_path.to_string_lossy().to_string(), None::<&str>)
+        .open_path(installer_path.to_string_lossy().to_string(), None::<&str>)
         .map_err(|e| AppError::tauri(format!("Failed to open installer: {}", e)))?;
 
     // DO NOT force app.exit() here - let the user/installer handle closure
@@ -149,6 +435,204 @@ pub async fn install_app_update(app: AppHandle, url: String) -> Result<String, A
 
     Ok(format!(
         "Installer launched! Please follow the installation prompts. File: {}",
-        msi_path.display()
+        installer_path.display()
     ))
 }
+
+/// Install an AppImage update.
+///
+/// When running from an AppImage (`$APPIMAGE` is set), the new image is
+/// downloaded next to it, atomically renamed over it, and launched before
+/// this instance exits. Otherwise the image is saved to the Downloads folder.
+async fn install_appimage(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    file_name: &str,
+    sha256: &str,
+) -> Result<String, AppError> {
+    let Some(current) = std::env::var_os("APPIMAGE").map(PathBuf::from) else {
+        let download_dir = app
+            .path()
+            .download_dir()
+            .map_err(|e| AppError::tauri(format!("Failed to resolve Downloads directory: {}", e)))?;
+        let destination = download_dir.join(file_name);
+
+        download_verified(app, client, url, &destination, sha256).await?;
+        make_executable(&destination)?;
+
+        return Ok(format!(
+            "AppImage saved to {}. Run it to use the new version.",
+            destination.display()
+        ));
+    };
+
+    // Download next to the running image so the final rename stays on one filesystem
+    let staged = current.with_file_name(format!(".{}.update", file_name));
+    download_verified(app, client, url, &staged, sha256).await?;
+    make_executable(&staged)?;
+
+    fs::rename(&staged, &current).map_err(|e| {
+        let _ = fs::remove_file(&staged);
+        AppError::logic(format!("Failed to replace {}: {}", current.display(), e))
+    })?;
+
+    std::process::Command::new(&current)
+        .spawn()
+        .map_err(|e| AppError::logic(format!("Update installed, but failed to restart: {}", e)))?;
+
+    app.exit(0);
+
+    Ok("Update installed! Restarting...".to_string())
+}
+
+/// Download an update package and check it against its published digest.
+///
+/// A package that does not match is deleted.
+async fn download_verified(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    destination: &Path,
+    sha256: &str,
+) -> Result<(), AppError> {
+    download_to_file(app, client, url, destination, MAX_APP_UPDATE_BYTES, "app-update").await?;
+
+    if let Err(e) = verify_sha256(destination, sha256, "update package") {
+        let _ = fs::remove_file(destination);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, draft: bool, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            draft,
+            prerelease,
+            body: None,
+            published_at: None,
+            assets: Vec::new(),
+        }
+    }
+
+    fn picked(releases: Vec<Release>, current: &str, beta_updates: bool) -> Option<String> {
+        let current = parse_version(current).unwrap();
+        pick_release(releases, &current, beta_updates)
+            .offer
+            .map(|(_, release)| release.tag_name)
+    }
+
+    const X64: &[&str] = &["x64", "amd64", "universal"];
+    const X86: &[&str] = &["x86", "i686", "i386"];
+    const ARM64: &[&str] = &["aarch64", "arm64", "universal"];
+
+    #[test]
+    fn matches_architecture_tokens() {
+        for name in [
+            "Godspeed_2.1.0_x64-setup.exe",
+            "Godspeed_2.1.0_x64_en-US.msi",
+            "godspeed_2.1.0_amd64.deb",
+            "godspeed-2.1.0-1.x86_64.rpm",
+            "Godspeed_2.1.0_x86-64.AppImage",
+            "Godspeed_2.1.0_universal.dmg",
+        ] {
+            assert!(name_matches_arch(name, X64), "rejected {:?}", name);
+        }
+
+        assert!(name_matches_arch("Godspeed_2.1.0_x86-setup.exe", X86));
+        assert!(name_matches_arch("godspeed-2.1.0-1.i686.rpm", X86));
+        assert!(name_matches_arch("Godspeed_2.1.0_aarch64.dmg", ARM64));
+    }
+
+    #[test]
+    fn does_not_match_other_architectures() {
+        for name in [
+            "Godspeed_2.1.0_x64-setup.exe",
+            "godspeed-2.1.0-1.x86_64.rpm",
+            "Godspeed_2.1.0_x86-64.AppImage",
+            "godspeed_2.1.0_amd64.deb",
+        ] {
+            assert!(!name_matches_arch(name, X86), "accepted {:?}", name);
+        }
+
+        assert!(!name_matches_arch("Godspeed_2.1.0_arm64.dmg", X64));
+        assert!(!name_matches_arch("godspeed_2.1.0_amd64.deb", ARM64));
+        assert!(!name_matches_arch("Godspeed_2.1.0_x86_64_debug.AppImage", ARM64));
+    }
+
+    #[test]
+    fn picks_the_highest_semver_release() {
+        let releases = vec![
+            release("v2.9.0", false, false),
+            release("v2.10.0", false, false),
+            release("v2.2.0", false, false),
+            release("nightly", false, false),
+        ];
+
+        assert_eq!(picked(releases, "2.1.1", false).as_deref(), Some("v2.10.0"));
+    }
+
+    #[test]
+    fn offers_prereleases_only_on_the_beta_channel() {
+        let releases = || {
+            vec![
+                release("v2.2.0", false, false),
+                release("v2.3.0-beta.1", false, false),
+                release("v2.4.0", false, true),
+            ]
+        };
+
+        assert_eq!(picked(releases(), "2.1.1", false).as_deref(), Some("v2.2.0"));
+        assert_eq!(picked(releases(), "2.1.1", true).as_deref(), Some("v2.4.0"));
+    }
+
+    #[test]
+    fn ignores_drafts() {
+        let releases = vec![release("v2.2.0", false, false), release("v3.0.0", true, false)];
+
+        assert_eq!(picked(releases, "2.1.1", true).as_deref(), Some("v2.2.0"));
+    }
+
+    #[test]
+    fn never_offers_the_current_or_a_lower_version() {
+        let releases = || {
+            vec![
+                release("v2.1.1", false, false),
+                release("v2.0.0", false, false),
+                release("v2.1.1-rc.1", false, true),
+            ]
+        };
+
+        assert_eq!(picked(releases(), "2.1.1", false), None);
+        assert_eq!(picked(releases(), "2.1.1", true), None);
+        assert_eq!(picked(releases(), "2.1.1-rc.1", true).as_deref(), Some("v2.1.1"));
+    }
+
+    #[test]
+    fn reports_the_latest_release_without_an_offer() {
+        let releases = || {
+            vec![
+                release("v2.1.0", false, false),
+                release("v2.2.0-beta.1", false, true),
+                release("v2.3.0", true, false),
+            ]
+        };
+        let current = parse_version("2.2.0-beta.1").unwrap();
+
+        let stable = pick_release(releases(), &current, false);
+        assert_eq!(stable.latest, parse_version("2.1.0"));
+        assert!(stable.offer.is_none());
+
+        let beta = pick_release(releases(), &current, true);
+        assert_eq!(beta.latest, parse_version("2.2.0-beta.1"));
+        assert!(beta.offer.is_none());
+
+        assert!(pick_release(Vec::new(), &current, true).latest.is_none());
+    }
+}
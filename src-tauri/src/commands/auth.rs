@@ -0,0 +1,601 @@
+//! Authentication profile commands.
+//!
+//! Auth profiles give yt-dlp access to members-only, age-gated and private
+//! content. Each profile applies to a set of sites and supplies a cookies
+//! file, cookies from a browser, a username and password, or a `.netrc`
+//! file. Passwords and imported cookies are kept in an encrypted
+//! [`SecretStore`] and only reach yt-dlp through private temporary files,
+//! never through the command line or the progress log.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::commands::settings::SettingsState;
+use crate::config::{AUTH_PROFILES_FILENAME, AUTH_SECRETS_FILENAME, MAX_COOKIES_FILE_BYTES, YT_DLP_COOKIE_BROWSERS};
+use crate::error::AppError;
+use crate::utils::paths::app_data_path;
+use crate::utils::network::NetworkConfig;
+use crate::utils::secrets::{app_secret_store, create_private_dir, write_private, SecretStore};
+use crate::utils::shlex::shlex_quote;
+use crate::utils::validation::{host_matches, validate_site};
+
+/// Secrets shorter than this are not redacted from log lines, since they
+/// would match ordinary output.
+const MIN_REDACTED_LEN: usize = 4;
+
+/// Credentials applied to downloads from a set of sites.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthProfile {
+    /// Assigned when the profile is first saved.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Host names the profile applies to; subdomains are included.
+    pub sites: Vec<String>,
+    pub method: AuthMethod,
+}
+
+/// How a profile authenticates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// Netscape cookies file, imported into the secret store.
+    CookiesFile,
+    /// Cookies read by yt-dlp from a browser, optionally from a specific profile.
+    Browser {
+        browser: String,
+        #[serde(default)]
+        profile: Option<String>,
+    },
+    /// Username and password; the password is kept in the secret store.
+    Password { username: String },
+    /// Credentials from a `.netrc` file; `location` defaults to `~/.netrc`.
+    Netrc {
+        #[serde(default)]
+        location: Option<String>,
+    },
+}
+
+impl AuthMethod {
+    /// Whether the method keeps a secret in the secret store.
+    fn has_secret(&self) -> bool {
+        matches!(self, AuthMethod::CookiesFile | AuthMethod::Password { .. })
+    }
+
+    /// Whether two methods store the same kind of secret.
+    fn same_kind(&self, other: &AuthMethod) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl AuthProfile {
+    fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::logic("Auth profile name is empty."));
+        }
+        if self.sites.is_empty() {
+            return Err(AppError::logic("Add at least one site to the auth profile."));
+        }
+        for site in &self.sites {
+            validate_site(site)?;
+        }
+
+        match &self.method {
+            AuthMethod::CookiesFile => {}
+            AuthMethod::Browser { browser, profile } => {
+                if !YT_DLP_COOKIE_BROWSERS.contains(&browser.as_str()) {
+                    return Err(AppError::logic(format!("Unsupported browser: {}", browser)));
+                }
+                if profile
+                    .as_deref()
+                    .is_some_and(|profile| profile.contains("::") || profile.chars().any(char::is_control))
+                {
+                    return Err(AppError::logic("Invalid browser profile name."));
+                }
+            }
+            AuthMethod::Password { username } => {
+                if username.trim().is_empty() || username.chars().any(char::is_control) {
+                    return Err(AppError::logic("Username is empty or invalid."));
+                }
+            }
+            AuthMethod::Netrc { location } => {
+                if let Some(location) = location {
+                    if !Path::new(location).is_file() {
+                        return Err(AppError::logic(format!("netrc file not found: {}", location)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tauri-managed state holding the auth profiles.
+///
+/// The lock also serializes access to the secret store.
+#[derive(Default)]
+pub struct AuthState(Mutex<Vec<AuthProfile>>);
+
+impl AuthState {
+    pub fn new(profiles: Vec<AuthProfile>) -> Self {
+        Self(Mutex::new(profiles))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<AuthProfile>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// yt-dlp arguments for the auth profile that applies to a URL.
+///
+/// Secrets are written to private temporary files that are removed when
+/// the session is dropped, so it must outlive the yt-dlp process.
+#[derive(Default)]
+pub struct AuthSession {
+    args: Vec<String>,
+    secrets: Vec<String>,
+    temp_dir: Option<PathBuf>,
+}
+
+impl AuthSession {
+    /// Arguments to pass to yt-dlp before the URL.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Mask any secret of the session that appears in a line of output.
+    pub fn redact(&self, line: &str) -> String {
+        self.secrets
+            .iter()
+            .filter(|secret| secret.len() >= MIN_REDACTED_LEN)
+            .fold(line.to_string(), |line, secret| line.replace(secret.as_str(), "********"))
+    }
+    /// Write a file into the session's private temporary directory,
+    /// creating the directory on first use.
+    fn write_private(&mut self, name: &str, content: &str) -> Result<PathBuf, AppError> {
+        let dir = match self.temp_dir {
+            Some(ref dir) => dir.clone(),
+            None => {
+                let dir = std::env::temp_dir().join(format!("godspeed-auth-{}", uuid::Uuid::new_v4()));
+                create_private_dir(&dir)?;
+                self.temp_dir = Some(dir.clone());
+                dir
+            }
+        };
+
+        let path = dir.join(name);
+        write_private(&path, content.as_bytes())?;
+        Ok(path)
+    }
+}
+
+impl Drop for AuthSession {
+    fn drop(&mut self) {
+        if let Some(ref dir) = self.temp_dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Prepare the auth arguments for a download URL.
+///
+/// The first profile whose sites match the URL's host is used. The proxy
+/// from the network settings is passed the same way as a password, since
+/// its URL may carry credentials. URLs without a matching profile and
+/// without a proxy get an empty session.
+///
+/// # Arguments
+/// * `app` - App handle used to read the profiles, settings and secret store
+/// * `url` - Normalized download URL
+///
+/// # Returns
+/// * `Ok(AuthSession)` with the arguments for yt-dlp
+/// * `Err(AppError)` if the profile's secret cannot be read or written out
+pub fn auth_session(app: &AppHandle, url: &str) -> Result<AuthSession, AppError> {
+    let network = app.state::<SettingsState>().network();
+
+    let host = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+
+    let state = app.state::<AuthState>();
+    let profiles = state.lock();
+    let profile = host.and_then(|host| profiles.iter().find(|profile| host_matches(&host, &profile.sites)));
+
+    let secret = match profile {
+        Some(profile) if profile.method.has_secret() => secret_store(app)?.load()?.remove(&profile.id),
+        _ => None,
+    };
+
+    build_session(profile, secret, &network)
+}
+
+/// Build the session for a profile (if any) and its stored secret.
+///
+/// Passwords and the proxy go into a private yt-dlp config file and
+/// imported cookies into a private cookies file; only the paths of those
+/// files appear in the arguments.
+fn build_session(
+    profile: Option<&AuthProfile>,
+    secret: Option<String>,
+    network: &NetworkConfig,
+) -> Result<AuthSession, AppError> {
+    let mut session = AuthSession::default();
+
+    // Options for a private config file, which keeps secrets out of the process list
+    let mut config = network.yt_dlp_config();
+    if !config.is_empty() {
+        session.secrets.extend(network.proxy_password.clone());
+    }
+
+    if let Some(profile) = profile {
+        match (&profile.method, secret) {
+            (
+                AuthMethod::Browser {
+                    browser,
+                    profile: browser_profile,
+                },
+                _,
+            ) => {
+                let spec = match browser_profile {
+                    Some(browser_profile) => format!("{}:{}", browser, browser_profile),
+                    None => browser.clone(),
+                };
+                session.args = vec!["--cookies-from-browser".to_string(), spec];
+            }
+            (AuthMethod::Netrc { location }, _) => {
+                session.args.push("--netrc".to_string());
+                if let Some(location) = location {
+                    session.args.push("--netrc-location".to_string());
+                    session.args.push(location.clone());
+                }
+            }
+            (AuthMethod::Password { username }, Some(secret)) => {
+                config.push_str(&format!(
+                    "--username {}\n--password {}\n",
+                    shlex_quote(username),
+                    shlex_quote(&secret)
+                ));
+                session.secrets.push(secret);
+            }
+            (AuthMethod::CookiesFile, Some(secret)) => {
+                let cookies = session.write_private("cookies.txt", &secret)?;
+                session.args = vec!["--cookies".to_string(), cookies.to_string_lossy().to_string()];
+                session.secrets.extend(cookie_values(&secret));
+            }
+            (AuthMethod::Password { .. } | AuthMethod::CookiesFile, None) => {
+                return Err(AppError::SecretStoreFailed(format!(
+                    "No saved credentials for auth profile \"{}\"; enter them again.",
+                    profile.name
+                )));
+            }
+        }
+    }
+
+    if !config.is_empty() {
+        let config = session.write_private("yt-dlp.conf", &config)?;
+        session.args.push("--config-locations".to_string());
+        session.args.push(config.to_string_lossy().to_string());
+    }
+
+    Ok(session)
+}
+
+/// Cookie lines of a Netscape cookies file (`#HttpOnly_` lines included).
+fn cookie_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .map(|line| line.strip_prefix("#HttpOnly_").unwrap_or(line).trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+}
+
+/// The values of all cookies in a Netscape cookies file.
+fn cookie_values(content: &str) -> Vec<String> {
+    cookie_lines(content)
+        .filter_map(|line| line.split('\t').nth(6))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Read a Netscape cookies file for import.
+fn read_cookies_file(path: &Path) -> Result<String, AppError> {
+    if fs::metadata(path)?.len() > MAX_COOKIES_FILE_BYTES {
+        return Err(AppError::logic("Cookies file is too large."));
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| AppError::logic(format!("Failed to read cookies file: {}", e)))?;
+
+    let is_valid = {
+        let mut cookies = cookie_lines(&content).peekable();
+        cookies.peek().is_some() && cookies.all(|line| line.split('\t').count() == 7)
+    };
+    if !is_valid {
+        return Err(AppError::logic(format!(
+            "Not a Netscape format cookies file: {}",
+            path.display()
+        )));
+    }
+
+    Ok(content)
+}
+
+/// Resolve the auth profiles file in the app data directory.
+fn profiles_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app_data_path(app, AUTH_PROFILES_FILENAME)
+}
+
+/// Open the auth profiles' secret store.
+fn secret_store(app: &AppHandle) -> Result<SecretStore, AppError> {
+    app_secret_store(app, AUTH_SECRETS_FILENAME)
+}
+
+/// Load the auth profiles from disk.
+///
+/// A missing or corrupt file yields no profiles.
+pub fn load_auth_profiles(app: &AppHandle) -> Vec<AuthProfile> {
+    profiles_file_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write the auth profiles to disk.
+fn persist(app: &AppHandle, profiles: &[AuthProfile]) -> Result<(), AppError> {
+    let path = profiles_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(profiles)
+        .map_err(|e| AppError::logic(format!("Failed to serialize auth profiles: {}", e)))?;
+    fs::write(&path, json)?;
+
+    Ok(())
+}
+
+/// List the auth profiles. Secrets are never returned.
+#[tauri::command]
+pub async fn list_auth_profiles(state: State<'_, AuthState>) -> Result<Vec<AuthProfile>, AppError> {
+    Ok(state.lock().clone())
+}
+
+/// Create or update an auth profile.
+///
+/// `password` (password profiles) and `cookies_file` (path of a Netscape
+/// cookies file to import, for cookies profiles) are required when a profile
+/// starts using that method; `None` keeps the stored secret. Returns the
+/// profile as stored.
+#[tauri::command]
+pub async fn save_auth_profile(
+    app: AppHandle,
+    state: State<'_, AuthState>,
+    profile: AuthProfile,
+    password: Option<String>,
+    cookies_file: Option<String>,
+) -> Result<AuthProfile, AppError> {
+    let method = match profile.method {
+        AuthMethod::Browser {
+            browser,
+            profile: browser_profile,
+        } => AuthMethod::Browser {
+            browser,
+            profile: browser_profile.filter(|name| !name.trim().is_empty()),
+        },
+        AuthMethod::Netrc { location } => AuthMethod::Netrc {
+            location: location.filter(|location| !location.trim().is_empty()),
+        },
+        method => method,
+    };
+    let profile = AuthProfile {
+        id: if profile.id.is_empty() {
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            profile.id
+        },
+        name: profile.name.trim().to_string(),
+        sites: profile.sites.iter().map(|site| site.trim().to_ascii_lowercase()).collect(),
+        method,
+    };
+    profile.validate()?;
+
+    let secret = match profile.method {
+        AuthMethod::CookiesFile => cookies_file
+            .map(|path| read_cookies_file(Path::new(&path)))
+            .transpose()?,
+        AuthMethod::Password { .. } => password.filter(|password| !password.is_empty()),
+        _ => None,
+    };
+
+    let mut profiles = state.lock();
+    let existing = profiles.iter().position(|stored| stored.id == profile.id);
+
+    let store = secret_store(&app)?;
+    let mut secrets = store.load_for_update()?;
+
+    match secret {
+        Some(secret) => {
+            secrets.insert(profile.id.clone(), secret);
+        }
+        None if profile.method.has_secret() => {
+            let keeps_secret = existing.is_some_and(|index| profiles[index].method.same_kind(&profile.method))
+                && secrets.contains_key(&profile.id);
+            if !keeps_secret {
+                return Err(AppError::logic(match profile.method {
+                    AuthMethod::CookiesFile => "Select a cookies file for the auth profile.",
+                    _ => "Enter a password for the auth profile.",
+                }));
+            }
+        }
+        None => {
+            secrets.remove(&profile.id);
+        }
+    }
+    store.save(&secrets)?;
+
+    match existing {
+        Some(index) => profiles[index] = profile.clone(),
+        None => profiles.push(profile.clone()),
+    }
+    persist(&app, &profiles)?;
+
+    Ok(profile)
+}
+
+/// Delete an auth profile and its stored secret.
+#[tauri::command]
+pub async fn delete_auth_profile(app: AppHandle, state: State<'_, AuthState>, id: String) -> Result<(), AppError> {
+    let mut profiles = state.lock();
+
+    let Some(index) = profiles.iter().position(|profile| profile.id == id) else {
+        return Err(AppError::logic(format!("Auth profile not found: {}", id)));
+    };
+
+    let store = secret_store(&app)?;
+    let mut secrets = store.load_for_update()?;
+    if secrets.remove(&id).is_some() {
+        store.save(&secrets)?;
+    }
+
+    profiles.remove(index);
+    persist(&app, &profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOKIES: &str = "# Netscape HTTP Cookie File\n\
+                           .example.com\tTRUE\t/\tTRUE\t0\tSID\tsession-cookie-value\n\
+                           #HttpOnly_.example.com\tTRUE\t/\tTRUE\t0\tHSID\thttp-only-value\r\n\
+                           \n";
+
+    fn profile(method: AuthMethod) -> AuthProfile {
+        AuthProfile {
+            id: "id".to_string(),
+            name: "Example".to_string(),
+            sites: vec!["example.com".to_string()],
+            method,
+        }
+    }
+
+    fn read(path: &str) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn parses_netscape_cookies() {
+        assert_eq!(cookie_values(COOKIES), ["session-cookie-value", "http-only-value"]);
+
+        let dir = std::env::temp_dir().join(format!("godspeed-cookies-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let valid = dir.join("valid.txt");
+        fs::write(&valid, COOKIES).unwrap();
+        assert_eq!(read_cookies_file(&valid).unwrap(), COOKIES);
+
+        for (name, content) in [
+            ("empty.txt", "# Netscape HTTP Cookie File\n"),
+            ("short.txt", ".example.com\tTRUE\t/\tTRUE\t0\tSID\n"),
+            ("json.txt", r#"[{"name":"SID","value":"x"}]"#),
+        ] {
+            let path = dir.join(name);
+            fs::write(&path, content).unwrap();
+            assert!(
+                matches!(read_cookies_file(&path), Err(AppError::Logic(_))),
+                "accepted {:?}",
+                content
+            );
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn redacts_session_secrets() {
+        let mut session = AuthSession::default();
+        session.secrets = vec!["hunter2-password".to_string(), "session-cookie-value".to_string(), "abc".to_string()];
+
+        assert_eq!(
+            session.redact("ERROR: login as bob with hunter2-password failed (cookie session-cookie-value)"),
+            "ERROR: login as bob with ******** failed (cookie ********)"
+        );
+        // Secrets too short to tell apart from ordinary output are left alone
+        assert_eq!(session.redact("[download] abc"), "[download] abc");
+    }
+
+    #[test]
+    fn passwords_only_reach_yt_dlp_through_a_private_config() {
+        let network = NetworkConfig {
+            proxy: "socks5://user@proxy:1080".to_string(),
+            proxy_password: Some("proxy-secret".to_string()),
+            ..NetworkConfig::default()
+        };
+        let profile = profile(AuthMethod::Password {
+            username: "bob".to_string(),
+        });
+
+        let session = build_session(Some(&profile), Some("it's secret".to_string()), &network).unwrap();
+
+        let args = session.args();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0], "--config-locations");
+        assert!(args.iter().all(|arg| !arg.contains("secret")), "secret in {:?}", args);
+
+        let config = read(&args[1]);
+        assert!(config.contains("--username 'bob'\n"));
+        assert!(config.contains(r#"--password 'it'"'"'s secret'"#));
+        assert!(config.contains("proxy-secret"));
+        assert_eq!(session.redact("it's secret proxy-secret"), "******** ********");
+
+        let dir = session.temp_dir.clone().unwrap();
+        drop(session);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn imported_cookies_reach_yt_dlp_through_a_private_file() {
+        let session = build_session(
+            Some(&profile(AuthMethod::CookiesFile)),
+            Some(COOKIES.to_string()),
+            &NetworkConfig::default(),
+        )
+        .unwrap();
+
+        let args = session.args();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0], "--cookies");
+        assert_eq!(read(&args[1]), COOKIES);
+        assert_eq!(session.redact("SID=session-cookie-value"), "SID=********");
+    }
+
+    #[test]
+    fn sessions_without_secrets_write_no_files() {
+        let session = build_session(None, None, &NetworkConfig::default()).unwrap();
+        assert!(session.args().is_empty());
+        assert!(session.temp_dir.is_none());
+
+        let browser = profile(AuthMethod::Browser {
+            browser: "firefox".to_string(),
+            profile: Some("work".to_string()),
+        });
+        let session = build_session(Some(&browser), None, &NetworkConfig::default()).unwrap();
+        assert_eq!(session.args(), ["--cookies-from-browser", "firefox:work"]);
+        assert!(session.temp_dir.is_none());
+
+        let password = profile(AuthMethod::Password {
+            username: "bob".to_string(),
+        });
+        assert!(matches!(
+            build_session(Some(&password), None, &NetworkConfig::default()),
+            Err(AppError::SecretStoreFailed(_))
+        ));
+    }
+}
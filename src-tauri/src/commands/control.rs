@@ -0,0 +1,265 @@
+//! Download control commands.
+//!
+//! Keeps a registry of running yt-dlp processes keyed by job ID and provides
+//! commands to cancel, pause and resume downloads.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use tauri::{AppHandle, State};
+use tauri_plugin_shell::process::CommandChild;
+
+use crate::commands::downloader::{emit_log, remove_partial_files, run_download, DownloadRequest};
+use crate::commands::queue::{self, JobStatus, QueueState};
+use crate::error::AppError;
+use crate::utils::process::kill_process_tree;
+
+/// Why a running download was stopped by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Cancelled,
+    Paused,
+}
+
+/// A download that is currently running, with its yt-dlp process if one is active.
+struct RunningDownload {
+    child: Option<CommandChild>,
+    stop: Option<StopReason>,
+}
+
+/// A download that was paused, with enough state to resume or discard it.
+struct PausedDownload {
+    request: DownloadRequest,
+    partial_files: Vec<PathBuf>,
+}
+
+/// Tauri-managed registry of running and paused downloads.
+#[derive(Default)]
+pub struct ProcessRegistry {
+    running: Mutex<HashMap<String, RunningDownload>>,
+    paused: Mutex<HashMap<String, PausedDownload>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl ProcessRegistry {
+    /// Track a job before its first yt-dlp process is spawned.
+    ///
+    /// The job can be cancelled or paused from this point on. Reserving a
+    /// job that is already tracked keeps a stop requested in the meantime.
+    pub fn reserve(&self, job_id: &str) {
+        lock(&self.paused).remove(job_id);
+        lock(&self.running)
+            .entry(job_id.to_string())
+            .or_insert(RunningDownload { child: None, stop: None });
+    }
+
+    /// Register a freshly spawned yt-dlp process under its job ID.
+    ///
+    /// If the job was stopped while no process was running, the process is
+    /// killed right away and the stop reason is kept for [`Self::unregister`].
+    pub fn register(&self, job_id: &str, child: CommandChild) {
+        lock(&self.paused).remove(job_id);
+
+        let stopped = {
+            let mut running = lock(&self.running);
+            let download = running
+                .entry(job_id.to_string())
+                .or_insert(RunningDownload { child: None, stop: None });
+            if download.stop.is_some() {
+                Some(child)
+            } else {
+                download.child = Some(child);
+                None
+            }
+        };
+
+        if let Some(child) = stopped {
+            if kill_process_tree(child.pid()).is_err() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    /// Forget a job's finished yt-dlp process while the job goes on.
+    ///
+    /// Returns the stop reason if the job was cancelled or paused meanwhile.
+    pub fn detach(&self, job_id: &str) -> Option<StopReason> {
+        lock(&self.running).get_mut(job_id).and_then(|download| {
+            download.child = None;
+            download.stop
+        })
+    }
+
+    /// Remove a finished process from the registry.
+    ///
+    /// Returns the stop reason if the process was cancelled or paused.
+    pub fn unregister(&self, job_id: &str) -> Option<StopReason> {
+        lock(&self.running)
+            .remove(job_id)
+            .and_then(|download| download.stop)
+    }
+
+    /// Check whether a job ID is already in use.
+    pub fn contains(&self, job_id: &str) -> bool {
+        lock(&self.running).contains_key(job_id) || lock(&self.paused).contains_key(job_id)
+    }
+
+    /// Keep a paused download so it can be resumed or cancelled later.
+    pub fn park(&self, job_id: &str, request: DownloadRequest, partial_files: Vec<PathBuf>) {
+        lock(&self.paused).insert(
+            job_id.to_string(),
+            PausedDownload {
+                request,
+                partial_files,
+            },
+        );
+    }
+
+    /// Stop a running process, killing yt-dlp and its aria2c/FFmpeg children.
+    ///
+    /// Returns `false` if no download is running under the job ID.
+    fn stop(&self, job_id: &str, reason: StopReason) -> Result<bool, AppError> {
+        let child = {
+            let mut running = lock(&self.running);
+            let Some(download) = running.get_mut(job_id) else {
+                return Ok(false);
+            };
+            download.stop = Some(reason);
+            download.child.take()
+        };
+
+        if let Some(child) = child {
+            if kill_process_tree(child.pid()).is_err() {
+                child
+                    .kill()
+                    .map_err(|e| AppError::tauri(format!("Failed to kill yt-dlp: {}", e)))?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn unpark(&self, job_id: &str) -> Option<PausedDownload> {
+        lock(&self.paused).remove(job_id)
+    }
+
+    /// Forget a paused download and remove its partial files.
+    ///
+    /// Returns `false` if no download is paused under the job ID.
+    pub fn discard_paused(&self, job_id: &str) -> bool {
+        match self.unpark(job_id) {
+            Some(paused) => {
+                remove_partial_files(&paused.partial_files);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Move a queued job from one of the `from` states to `to`.
+///
+/// Returns `false` if the job is not in the queue or in another state.
+fn set_queued_status(app: &AppHandle, job_id: &str, from: &[JobStatus], to: JobStatus) -> bool {
+    let mut changed = false;
+    queue::update_job(app, job_id, |job| {
+        if from.contains(&job.status) {
+            job.status = to;
+            changed = true;
+        }
+    });
+    changed
+}
+
+/// Cancel a download.
+///
+/// Kills yt-dlp together with the aria2c/FFmpeg processes it started and
+/// removes the partial files. Pending and paused jobs are cancelled as well.
+#[tauri::command]
+pub async fn cancel_download(
+    app: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+    job_id: String,
+) -> Result<(), AppError> {
+    // Running jobs clean up their own partial files once the process exits
+    if registry.stop(&job_id, StopReason::Cancelled)? {
+        emit_log(&app, &job_id, "[GODSPEED] Cancelling download...");
+        return Ok(());
+    }
+
+    let parked = registry.discard_paused(&job_id);
+
+    let queued = set_queued_status(
+        &app,
+        &job_id,
+        &[JobStatus::Pending, JobStatus::Paused],
+        JobStatus::Cancelled,
+    );
+
+    if !parked && !queued {
+        return Err(AppError::logic(format!("No active download with ID: {}", job_id)));
+    }
+
+    emit_log(&app, &job_id, "[GODSPEED] Download cancelled.");
+    Ok(())
+}
+
+/// Pause a download.
+///
+/// Stops the yt-dlp process tree but keeps the partial files, so the job
+/// can continue where it left off. Pending queue jobs are held back.
+#[tauri::command]
+pub async fn pause_download(
+    app: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+    job_id: String,
+) -> Result<(), AppError> {
+    if registry.stop(&job_id, StopReason::Paused)? {
+        emit_log(&app, &job_id, "[GODSPEED] Pausing download...");
+        return Ok(());
+    }
+
+    if set_queued_status(&app, &job_id, &[JobStatus::Pending], JobStatus::Paused) {
+        return Ok(());
+    }
+
+    Err(AppError::logic(format!("No running download with ID: {}", job_id)))
+}
+
+/// Resume a paused download.
+///
+/// Queued jobs go back to the queue; standalone downloads are restarted
+/// in the background and report through the usual download events.
+#[tauri::command]
+pub async fn resume_download(
+    app: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+    queue_state: State<'_, QueueState>,
+    job_id: String,
+) -> Result<(), AppError> {
+    let parked = registry.unpark(&job_id);
+
+    if queue_state.contains(&job_id) {
+        if !set_queued_status(&app, &job_id, &[JobStatus::Paused], JobStatus::Pending) {
+            return Err(AppError::logic(format!("Job is not paused: {}", job_id)));
+        }
+        queue::pump_queue(&app);
+        return Ok(());
+    }
+
+    let Some(paused) = parked else {
+        return Err(AppError::logic(format!("No paused download with ID: {}", job_id)));
+    };
+
+    emit_log(&app, &job_id, "[GODSPEED] Resuming download...");
+
+    tauri::async_runtime::spawn(async move {
+        let _ = run_download(&app, &job_id, &paused.request).await;
+    });
+
+    Ok(())
+}
@@ -2,127 +2,784 @@
 //!
 //! Provides the main download_video command using yt-dlp sidecar.
 
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use tauri::Emitter;
-use tauri_plugin_shell::process::CommandEvent;
+use tauri::Manager;
+use tauri_plugin_shell::process::{Command, CommandEvent, Output};
 use tauri_plugin_shell::ShellExt;
 
-use crate::config::{get_audio_bitrate, EVENT_DOWNLOAD_COMPLETE, EVENT_DOWNLOAD_PROGRESS, YT_DLP_SIDECAR};
+use crate::commands::auth::{auth_session, AuthSession};
+use crate::commands::control::{ProcessRegistry, StopReason};
+use crate::commands::history;
+use crate::commands::settings::SettingsState;
+use crate::config::{
+    AudioFormat, TagOptions, VideoOptions, DEFAULT_FILENAME_TEMPLATE, DOWNLOAD_ARCHIVE_FILENAME, EVENT_DOWNLOAD_COMPLETE,
+    EVENT_DOWNLOAD_LOG, EVENT_DOWNLOAD_PROGRESS, FFMPEG_SIDECAR, YT_DLP_DOWNLOAD_TEMPLATE, YT_DLP_EXIT_ALREADY_ARCHIVED,
+    YT_DLP_FILENAME_TEMPLATE, YT_DLP_FILEPATH_TEMPLATE, YT_DLP_POSTPROCESS_TEMPLATE, YT_DLP_SIDECAR,
+};
 use crate::error::AppError;
+use crate::utils::network::NetworkConfig;
+use crate::utils::paths::app_data_path;
+use crate::utils::progress::{
+    parse_output_line, CompleteEvent, FinishedFile, LogEvent, OutputLine, ProgressEvent, ProgressPhase,
+};
+use crate::utils::shlex::shlex_quote;
+use crate::utils::template::{build_output_template, validate_template, with_suffix};
+use crate::utils::validation::{canonicalize_output_dir, normalize_url};
+
+/// Parameters for a single yt-dlp download job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadRequest {
+    pub url: String,
+    pub output_path: String,
+    pub mode: DownloadMode,
+    /// Tag and cover art embedding.
+    #[serde(default)]
+    pub tags: TagOptions,
+    /// Filename template relative to `output_path`, without extension;
+    /// `None` uses the template from the settings.
+    #[serde(default)]
+    pub filename_template: Option<String>,
+    /// What to do if the item was downloaded before.
+    #[serde(default)]
+    pub duplicates: DuplicatePolicy,
+    /// Set when this job is a single entry of a playlist or channel.
+    #[serde(default)]
+    pub playlist: Option<PlaylistItem>,
+}
+
+/// What to download: extracted audio or merged video.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DownloadMode {
+    Audio { format: AudioFormat },
+    Video { options: VideoOptions },
+}
+
+impl DownloadMode {
+    fn validate(&self) -> Result<(), AppError> {
+        match self {
+            DownloadMode::Audio { format } => format.validate(),
+            DownloadMode::Video { options } => options.validate(),
+        }
+    }
+
+    fn yt_dlp_args(&self) -> Vec<String> {
+        match self {
+            DownloadMode::Audio { format } => format.yt_dlp_args(),
+            DownloadMode::Video { options } => options.yt_dlp_args(),
+        }
+    }
+
+    /// Extension of the final file after post-processing, if known in advance.
+    fn output_extension(&self) -> Option<&'static str> {
+        match self {
+            DownloadMode::Audio { format } => format.extension(),
+            DownloadMode::Video { options } => Some(options.container.extension()),
+        }
+    }
+}
+
+/// How to handle an item that was downloaded before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Skip items recorded in the download archive or whose file already exists.
+    #[default]
+    Skip,
+    /// Download again and replace the existing file.
+    Overwrite,
+    /// Download again under a numbered name, e.g. `Title (1).mp3`.
+    Rename,
+}
+
+/// Why a download was skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The item is recorded in the app's download archive.
+    InArchive,
+    /// The output file already exists.
+    FileExists,
+}
+
+/// A playlist entry selected for download.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    /// 1-based index of the entry within the playlist.
+    pub index: u32,
+    /// Prefix the filename with `%(playlist_index)s`.
+    pub numbered: bool,
+}
+
+impl DownloadRequest {
+    /// Validate the download options.
+    fn validate(&self) -> Result<(), AppError> {
+        self.mode.validate()?;
+        let (audio_format, video_container) = match self.mode {
+            DownloadMode::Audio { ref format } => (Some(format), None),
+            DownloadMode::Video { ref options } => (None, Some(options.container)),
+        };
+        self.tags.validate(audio_format, video_container)?;
+
+        if let Some(ref template) = self.filename_template {
+            validate_template(template)?;
+        }
+
+        Ok(())
+    }
+
+    /// Normalize the URL and output folder, then validate the request.
+    ///
+    /// Every request passes through here before yt-dlp is spawned for it.
+    /// `allowed_sites` is the site allowlist from the settings.
+    pub fn normalized(self, allowed_sites: &[String]) -> Result<Self, AppError> {
+        let request = Self {
+            url: normalize_url(&self.url, allowed_sites)?,
+            output_path: canonicalize_output_dir(&self.output_path)?
+                .to_string_lossy()
+                .to_string(),
+            ..self
+        };
+        request.validate()?;
+
+        Ok(request)
+    }
+}
+
+/// Final outcome of a download job that did not fail.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DownloadOutcome {
+    Completed { file_path: Option<String> },
+    /// Nothing was downloaded; `file_path` is the existing file, if known.
+    Skipped {
+        reason: SkipReason,
+        file_path: Option<String>,
+    },
+    Paused,
+    Cancelled,
+}
 
 /// Download a video/audio from URL using yt-dlp.
 ///
 /// Uses aria2c for high-performance parallel downloading and FFmpeg
-/// for audio extraction and conversion.
+/// for audio extraction/conversion or video merging. The optional `job_id` lets the
+/// frontend cancel or pause the download; one is generated if omitted.
 #[tauri::command]
 pub async fn download_video(
     app: AppHandle,
-    url: String,
-    output_path: String,
-    quality: String,
-) -> Result<String, AppError> {
-    // Map quality string to exact bitrate
-    let audio_bitrate = get_audio_bitrate(&quality);
+    request: DownloadRequest,
+    job_id: Option<String>,
+) -> Result<DownloadOutcome, AppError> {
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-    // Build the output template
-    let output_template = format!("{}/%(title)s.%(ext)s", output_path);
+    if app.state::<ProcessRegistry>().contains(&job_id) {
+        return Err(AppError::logic(format!("Job ID already in use: {}", job_id)));
+    }
+
+    let request = DownloadRequest {
+        playlist: None,
+        ..request
+    }
+    .normalized(&app.state::<SettingsState>().allowed_sites())?;
+
+    run_download(&app, &job_id, &request).await
+}
+
+/// Run a single yt-dlp job to completion.
+///
+/// Shared by the `download_video` command, the download queue workers and
+/// `resume_download`. The job is tracked in the [`ProcessRegistry`] under
+/// `job_id` from the start, so it can be stopped during the duplicate check.
+pub(crate) async fn run_download(
+    app: &AppHandle,
+    job_id: &str,
+    request: &DownloadRequest,
+) -> Result<DownloadOutcome, AppError> {
+    let registry = app.state::<ProcessRegistry>();
+    registry.reserve(job_id);
+
+    let result = execute_download(app, job_id, request).await;
+    if result.is_err() {
+        registry.unregister(job_id);
+    }
+
+    result
+}
+
+/// Body of [`run_download`]; the caller unregisters the job if it fails.
+async fn execute_download(
+    app: &AppHandle,
+    job_id: &str,
+    request: &DownloadRequest,
+) -> Result<DownloadOutcome, AppError> {
+    let registry = app.state::<ProcessRegistry>();
+
+    // Jobs without their own template use the one from the settings
+    let templated;
+    let request = match request.filename_template {
+        Some(_) => request,
+        None => {
+            templated = DownloadRequest {
+                filename_template: Some(app.state::<SettingsState>().filename_template()),
+                ..request.clone()
+            };
+            &templated
+        }
+    };
+
+    // Every job records into the download archive. Only Skip jobs check the
+    // shared archive; the others record into a job archive merged afterwards
+    let archive = download_archive_path(app)?;
+    let job_archive = match request.duplicates {
+        DuplicatePolicy::Skip => None,
+        DuplicatePolicy::Overwrite | DuplicatePolicy::Rename => Some(job_archive_path(&archive, job_id)),
+    };
+    let run_archive = job_archive.as_deref().unwrap_or(&archive);
+
+    // Credentials for the site; temporary secret files live until the job ends
+    let auth = auth_session(app, &request.url)?;
+    let network = app.state::<SettingsState>().network();
+
+    // Rename jobs pick a free filename before anything is downloaded; Skip
+    // jobs are resolved by the download itself
+    let renamed;
+    let request = match check_duplicates(app, job_id, request, run_archive, &auth, &network).await? {
+        DuplicateCheck::Proceed => request,
+        DuplicateCheck::Rename(request) => {
+            renamed = *request;
+            &renamed
+        }
+        DuplicateCheck::Stopped(reason) => {
+            registry.unregister(job_id);
+            return Ok(stop_download(app, job_id, request, reason, Vec::new()));
+        }
+    };
 
     // Build the yt-dlp sidecar command
-    let sidecar_command = app
-        .shell()
-        .sidecar(YT_DLP_SIDECAR)
-        .map_err(|e| AppError::tauri(format!("Failed to create sidecar command: {}", e)))?
-        .args([
-            // === SAFETY FLAGS ===
-            "--no-playlist",
-            "--windows-filenames",
-            "--trim-filenames",
-            "200",
-            // === OUTPUT CONFIG ===
-            "-o",
-            &output_template,
-            // === AUDIO EXTRACTION ===
-            "--extract-audio",
-            "--audio-format",
-            "mp3",
-            "--audio-quality",
-            audio_bitrate,
-            // === HIGH-PERFORMANCE DOWNLOAD ===
-            "--external-downloader",
-            "aria2c",
-            "--external-downloader-args",
-            "-x 16 -k 1M",
-            // === TARGET URL ===
-            &url,
-        ]);
+    let sidecar_command =
+        yt_dlp_command(app, &network)?.args(build_download_args(request, run_archive, &auth, &network));
 
     // Spawn the command and get the receiver for events
-    let (mut rx, _child) = sidecar_command
+    let (mut rx, child) = sidecar_command
         .spawn()
-        .map_err(|e| AppError::tauri(format!("Failed to spawn yt-dlp: {}", e)))?;
+        .map_err(|e| AppError::EngineMissing(format!("Failed to start yt-dlp: {}", e)))?;
+
+    // Track the process so it can be cancelled or paused
+    registry.register(job_id, child);
+
+    let _ = app.emit(
+        EVENT_DOWNLOAD_PROGRESS,
+        ProgressEvent::phase(job_id, ProgressPhase::Extracting),
+    );
 
-    // Track the final output file path
-    let mut final_file_path: Option<String> = None;
+    // Track the finished output file and every file yt-dlp started writing
+    let mut finished_file: Option<FinishedFile> = None;
+    let mut existing_file: Option<String> = None;
+    let mut destinations: Vec<PathBuf> = Vec::new();
+    let mut exit_code: Option<i32> = None;
+
+    // Keep yt-dlp's error lines to classify a failed run
+    let mut error_lines = String::new();
 
     // Listen for stdout/stderr events and emit progress to frontend
     while let Some(event) = rx.recv().await {
         match event {
-            CommandEvent::Stdout(line) => {
-                let line_str = String::from_utf8_lossy(&line).to_string();
-
-                // Try to capture the final destination path
-                // yt-dlp outputs: [ExtractAudio] Destination: C:\path\to\file.mp3
-                if line_str.contains("Destination:") {
-                    if let Some(path_start) = line_str.find("Destination:") {
-                        let path = line_str[path_start + 12..].trim().to_string();
-                        // Only keep the MP3 path (final output)
-                        if path.ends_with(".mp3") {
-                            final_file_path = Some(path.clone());
+            CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                let line_str = auth.redact(&String::from_utf8_lossy(&line));
+
+                match parse_output_line(job_id, &line_str) {
+                    Some(OutputLine::Progress { event, filename }) => {
+                        if let Some(filename) = filename.map(PathBuf::from) {
+                            if !destinations.contains(&filename) {
+                                destinations.push(filename);
+                            }
                         }
+                        let _ = app.emit(EVENT_DOWNLOAD_PROGRESS, event);
                     }
-                }
-
-                // Emit all meaningful output for terminal display
-                if !line_str.trim().is_empty() {
-                    let _ = app.emit(EVENT_DOWNLOAD_PROGRESS, &line_str);
-                }
-            }
-            CommandEvent::Stderr(line) => {
-                let line_str = String::from_utf8_lossy(&line).to_string();
-
-                // Also check stderr for Destination (yt-dlp sometimes uses it)
-                if line_str.contains("Destination:") {
-                    if let Some(path_start) = line_str.find("Destination:") {
-                        let path = line_str[path_start + 12..].trim().to_string();
-                        if path.ends_with(".mp3") {
-                            final_file_path = Some(path.clone());
+                    Some(OutputLine::FinalFile(file)) => {
+                        emit_log(app, job_id, &format!("[GODSPEED] Saved: {}", file.filepath));
+                        finished_file = Some(file);
+                    }
+                    Some(OutputLine::AlreadyDownloaded(path)) => {
+                        emit_log(app, job_id, line_str.trim_end());
+                        existing_file = Some(path);
+                    }
+                    // Emit all other meaningful output for terminal display
+                    None if !line_str.trim().is_empty() => {
+                        if line_str.trim_start().starts_with("ERROR:") {
+                            error_lines.push_str(line_str.trim_end());
+                            error_lines.push('\n');
                         }
+                        emit_log(app, job_id, line_str.trim_end());
                     }
-                }
-
-                // Emit stderr output (yt-dlp often outputs progress here)
-                if !line_str.trim().is_empty() {
-                    let _ = app.emit(EVENT_DOWNLOAD_PROGRESS, &line_str);
+                    None => {}
                 }
             }
             CommandEvent::Terminated(status) => {
-                if status.code == Some(0) {
-                    // Send completion with the file path
-                    if let Some(ref path) = final_file_path {
-                        let _ = app.emit(EVENT_DOWNLOAD_COMPLETE, path.clone());
-                    }
-                    let _ = app.emit(EVENT_DOWNLOAD_PROGRESS, "Download completed!");
-                } else {
-                    let _ = app.emit(
-                        EVENT_DOWNLOAD_PROGRESS,
-                        format!("[ERROR] Process exited with code: {:?}", status.code),
-                    );
-                }
+                exit_code = status.code;
             }
             _ => {}
         }
     }
 
-    // Return the final file path or a success message
-    Ok(final_file_path.unwrap_or_else(|| "Download completed".to_string()))
+    if let Some(ref job_archive) = job_archive {
+        if let Err(e) = merge_archive(job_archive, &archive) {
+            emit_log(app, job_id, &format!("[WARN] Failed to update the download archive: {}", e));
+        }
+    }
+
+    // Check whether the user stopped the process
+    if let Some(reason) = registry.unregister(job_id) {
+        return Ok(stop_download(app, job_id, request, reason, destinations));
+    }
+
+    let final_file_path = finished_file.as_ref().map(|file| file.filepath.clone());
+
+    // `--break-on-existing` stops at an archived item; an existing file is
+    // reported by yt-dlp and left untouched
+    if request.duplicates == DuplicatePolicy::Skip {
+        let skipped = match exit_code {
+            Some(YT_DLP_EXIT_ALREADY_ARCHIVED) => Some((SkipReason::InArchive, None)),
+            Some(0) if existing_file.is_some() => Some((SkipReason::FileExists, final_file_path.clone().or(existing_file))),
+            _ => None,
+        };
+
+        if let Some((reason, file_path)) = skipped {
+            let message = match reason {
+                SkipReason::InArchive => "[GODSPEED] Skipped: already in the download archive.".to_string(),
+                SkipReason::FileExists => format!(
+                    "[GODSPEED] Skipped: file already exists: {}",
+                    file_path.as_deref().unwrap_or_default()
+                ),
+            };
+            emit_log(app, job_id, &message);
+            return Ok(DownloadOutcome::Skipped { reason, file_path });
+        }
+    }
+
+    if exit_code != Some(0) {
+        let error = AppError::from_yt_dlp(&error_lines, exit_code);
+        emit_log(app, job_id, &format!("[ERROR] {}", error));
+        return Err(error);
+    }
+
+    if let Some(ref file) = finished_file {
+        if let Err(e) = history::record(app, request, file) {
+            emit_log(app, job_id, &format!("[WARN] Failed to save download history: {}", e));
+        }
+    }
+
+    // Send completion with the file path
+    let _ = app.emit(
+        EVENT_DOWNLOAD_PROGRESS,
+        ProgressEvent::phase(job_id, ProgressPhase::Finished),
+    );
+    let _ = app.emit(
+        EVENT_DOWNLOAD_COMPLETE,
+        CompleteEvent {
+            job_id: job_id.to_string(),
+            file_path: final_file_path.clone(),
+        },
+    );
+    emit_log(app, job_id, "Download completed!");
+
+    // Return the final file path
+    Ok(DownloadOutcome::Completed {
+        file_path: final_file_path,
+    })
+}
+
+/// Clean up after a download the user cancelled or paused.
+///
+/// Cancelled jobs lose their partial files; paused jobs keep them and are
+/// parked so they can be resumed.
+fn stop_download(
+    app: &AppHandle,
+    job_id: &str,
+    request: &DownloadRequest,
+    reason: StopReason,
+    partial_files: Vec<PathBuf>,
+) -> DownloadOutcome {
+    match reason {
+        StopReason::Cancelled => {
+            remove_partial_files(&partial_files);
+            emit_log(app, job_id, "[GODSPEED] Download cancelled.");
+            DownloadOutcome::Cancelled
+        }
+        StopReason::Paused => {
+            app.state::<ProcessRegistry>().park(job_id, request.clone(), partial_files);
+            emit_log(app, job_id, "[GODSPEED] Download paused.");
+            DownloadOutcome::Paused
+        }
+    }
+}
+
+/// Build the yt-dlp argument list for a download request.
+///
+/// `archive` is the download archive to record into; Skip jobs also stop
+/// at items already in it. `auth` supplies the credentials for the site and `network` the aria2c
+/// network options (yt-dlp's own are added by [`yt_dlp_command`]).
+fn build_download_args(
+    request: &DownloadRequest,
+    archive: &Path,
+    auth: &AuthSession,
+    network: &NetworkConfig,
+) -> Vec<String> {
+    // === SAFETY FLAGS ===
+    let mut args: Vec<String> = ["--windows-filenames", "--trim-filenames", "200"]
+        .map(String::from)
+        .to_vec();
+
+    // === PLAYLIST HANDLING ===
+    // Playlist entries are downloaded through the playlist URL so that
+    // yt-dlp can resolve %(playlist_index)s for the filename
+    match request.playlist {
+        Some(ref item) => {
+            args.extend(["--yes-playlist".to_string(), "--playlist-items".to_string(), item.index.to_string()]);
+        }
+        None => args.push("--no-playlist".to_string()),
+    }
+
+    // === OUTPUT CONFIG ===
+    let numbered = request.playlist.as_ref().is_some_and(|item| item.numbered);
+    let template = request
+        .filename_template
+        .as_deref()
+        .unwrap_or(DEFAULT_FILENAME_TEMPLATE);
+    let output = Path::new(&request.output_path).join(build_output_template(template, numbered));
+    args.push("-o".to_string());
+    args.push(output.to_string_lossy().to_string());
+
+    // === DUPLICATE HANDLING ===
+    args.push("--download-archive".to_string());
+    args.push(archive.to_string_lossy().to_string());
+    match request.duplicates {
+        DuplicatePolicy::Skip => {
+            args.extend(["--no-overwrites", "--no-post-overwrites", "--break-on-existing"].map(String::from));
+        }
+        DuplicatePolicy::Overwrite => args.push("--force-overwrites".to_string()),
+        DuplicatePolicy::Rename => {}
+    }
+
+    // === FORMAT SELECTION / AUDIO EXTRACTION ===
+    args.extend(request.mode.yt_dlp_args());
+
+    // === POST-PROCESSING ===
+    args.extend(request.tags.yt_dlp_args());
+
+    // Point yt-dlp at the bundled FFmpeg sidecar for merging and conversion
+    if let Some(ffmpeg) = sidecar_path(FFMPEG_SIDECAR) {
+        args.push("--ffmpeg-location".to_string());
+        args.push(ffmpeg.to_string_lossy().to_string());
+    }
+
+    // === HIGH-PERFORMANCE DOWNLOAD ===
+    // aria2c cannot use SOCKS proxies; yt-dlp then downloads by itself
+    if network.supports_aria2c() {
+        let aria2c_args = ["-x 16 -k 1M".to_string()]
+            .into_iter()
+            .chain(network.aria2c_args().iter().map(|arg| shlex_quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        args.extend([
+            "--external-downloader".to_string(),
+            "aria2c".to_string(),
+            "--external-downloader-args".to_string(),
+            aria2c_args,
+        ]);
+    }
+
+    // === MACHINE-READABLE OUTPUT ===
+    args.extend(
+        [
+            "--newline",
+            "--progress",
+            "--progress-template",
+            YT_DLP_DOWNLOAD_TEMPLATE,
+            "--progress-template",
+            YT_DLP_POSTPROCESS_TEMPLATE,
+            "--print",
+            YT_DLP_FILEPATH_TEMPLATE,
+        ]
+        .map(String::from),
+    );
+
+    // === AUTHENTICATION ===
+    args.extend_from_slice(auth.args());
+
+    // === TARGET URL ===
+    // `--` ends option parsing, so the URL can never be read as an option
+    args.push("--".to_string());
+    args.push(request.url.clone());
+
+    args
+}
+
+/// Resolve the on-disk path of a bundled sidecar binary.
+///
+/// Tauri places sidecars next to the app executable (without the target
+/// triple suffix), both in bundled builds and in `target/debug`.
+fn sidecar_path(name: &str) -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let path = exe_dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+
+    path.exists().then_some(path)
+}
+
+/// Outcome of checking a job for an earlier download of the same item.
+enum DuplicateCheck {
+    /// Download with the request as given.
+    Proceed,
+    /// Download with a numbered filename instead.
+    Rename(Box<DownloadRequest>),
+    /// The user cancelled or paused the job during the check.
+    Stopped(StopReason),
+}
+
+/// Pick a free numbered filename for a Rename job whose output file exists.
+///
+/// Runs yt-dlp in simulation mode with the job's own arguments to learn the
+/// exact output filename. The simulation is registered under `job_id`, so
+/// stopping the job kills it. Other policies are not checked, nor are jobs
+/// that cannot match anything (see [`may_have_duplicates`]).
+async fn check_duplicates(
+    app: &AppHandle,
+    job_id: &str,
+    request: &DownloadRequest,
+    archive: &Path,
+    auth: &AuthSession,
+    network: &NetworkConfig,
+) -> Result<DuplicateCheck, AppError> {
+    if request.duplicates != DuplicatePolicy::Rename || !may_have_duplicates(request) {
+        return Ok(DuplicateCheck::Proceed);
+    }
+
+    let mut args: Vec<String> = ["--simulate", "--print", YT_DLP_FILENAME_TEMPLATE]
+        .map(String::from)
+        .to_vec();
+    args.extend(build_download_args(request, archive, auth, network));
+
+    let (mut rx, child) = yt_dlp_command(app, network)?
+        .args(args)
+        .spawn()
+        .map_err(|e| AppError::EngineMissing(format!("Failed to start yt-dlp: {}", e)))?;
+
+    let registry = app.state::<ProcessRegistry>();
+    registry.register(job_id, child);
+
+    let mut stdout: Vec<String> = Vec::new();
+    let mut stderr = String::new();
+    let mut exit_code: Option<i32> = None;
+
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => stdout.push(String::from_utf8_lossy(&line).trim_end().to_string()),
+            CommandEvent::Stderr(line) => {
+                stderr.push_str(String::from_utf8_lossy(&line).trim_end());
+                stderr.push('\n');
+            }
+            CommandEvent::Terminated(status) => exit_code = status.code,
+            _ => {}
+        }
+    }
+
+    if let Some(reason) = registry.detach(job_id) {
+        return Ok(DuplicateCheck::Stopped(reason));
+    }
+
+    if exit_code != Some(0) {
+        return Err(AppError::from_yt_dlp(&auth.redact(&stderr), exit_code));
+    }
+
+    let resolved: String = stdout
+        .first()
+        .and_then(|line| serde_json::from_str(line).ok())
+        .ok_or_else(|| AppError::logic("Failed to resolve the output filename."))?;
+    let resolved = PathBuf::from(resolved);
+    let final_extension = request.mode.output_extension();
+
+    if existing_output(&resolved, final_extension).is_none() {
+        return Ok(DuplicateCheck::Proceed);
+    }
+
+    // Find the first free "<name> (n)" for both the download and final extension
+    let stem = resolved.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = resolved
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let numbered = |n: u32| resolved.with_file_name(format!("{} ({}){}", stem, n, extension));
+
+    let mut n = 1;
+    while existing_output(&numbered(n), final_extension).is_some() {
+        n += 1;
+    }
+
+    let template = request
+        .filename_template
+        .as_deref()
+        .unwrap_or(DEFAULT_FILENAME_TEMPLATE);
+
+    Ok(DuplicateCheck::Rename(Box::new(DownloadRequest {
+        filename_template: Some(with_suffix(template, &format!(" ({})", n))),
+        ..request.clone()
+    })))
+}
+
+/// Whether a job can collide with an existing file at all.
+///
+/// An existing output file can only be somewhere below a non-empty output folder.
+fn may_have_duplicates(request: &DownloadRequest) -> bool {
+    fs::read_dir(&request.output_path).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Find an existing file for a resolved output path, either as downloaded
+/// or with the extension it gets after post-processing.
+fn existing_output(path: &Path, final_extension: Option<&str>) -> Option<PathBuf> {
+    std::iter::once(path.to_path_buf())
+        .chain(final_extension.map(|ext| path.with_extension(ext)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Resolve the app-managed download archive in the app data directory.
+fn download_archive_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let path = app_data_path(app, DOWNLOAD_ARCHIVE_FILENAME)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(path)
+}
+
+/// Path of the per-job archive next to the shared download archive.
+fn job_archive_path(archive: &Path, job_id: &str) -> PathBuf {
+    let stem = archive.file_stem().unwrap_or_default().to_string_lossy();
+    archive.with_file_name(format!("{}-{}.txt", stem, job_id))
+}
+
+/// Append the entries of a job archive to the shared download archive and
+/// remove the job archive.
+fn merge_archive(job_archive: &Path, archive: &Path) -> Result<(), AppError> {
+    let entries = match fs::read_to_string(job_archive) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    if !entries.trim().is_empty() {
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(archive)?;
+        file.write_all(entries.as_bytes())?;
+    }
+    fs::remove_file(job_archive)?;
+
+    Ok(())
+}
+
+/// Create a yt-dlp sidecar command with the network settings applied.
+fn yt_dlp_command(app: &AppHandle, network: &NetworkConfig) -> Result<Command, AppError> {
+    network.validate()?;
+
+    let command = app
+        .shell()
+        .sidecar(YT_DLP_SIDECAR)
+        .map_err(|e| AppError::tauri(format!("Failed to create sidecar command: {}", e)))?
+        .args(network.yt_dlp_args());
+
+    Ok(network
+        .yt_dlp_env()
+        .into_iter()
+        .fold(command, |command, (key, value)| command.env(key, value)))
+}
+
+/// Run the yt-dlp sidecar to completion and collect its output.
+async fn run_yt_dlp<I, S>(app: &AppHandle, args: I) -> Result<Output, AppError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let network = app.state::<SettingsState>().network();
+
+    yt_dlp_command(app, &network)?
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| AppError::EngineMissing(format!("Failed to start yt-dlp: {}", e)))
+}
+
+/// Build the typed error for a failed yt-dlp run from its stderr.
+fn yt_dlp_failure(output: &Output, auth: &AuthSession) -> AppError {
+    AppError::from_yt_dlp(&auth.redact(&String::from_utf8_lossy(&output.stderr)), output.status.code())
+}
+
+/// Run the yt-dlp sidecar on a URL to completion and deserialize its JSON output.
+///
+/// Used for metadata-only invocations such as `-J`. The auth profile for
+/// the URL's site is applied, and the URL is passed after `--`.
+pub(crate) async fn run_yt_dlp_json<T: DeserializeOwned>(
+    app: &AppHandle,
+    options: &[&str],
+    url: &str,
+) -> Result<T, AppError> {
+    let auth = auth_session(app, url)?;
+
+    let mut args = options.to_vec();
+    args.extend(auth.args().iter().map(String::as_str));
+    args.extend(["--", url]);
+
+    let output = run_yt_dlp(app, &args).await?;
+
+    if !output.status.success() {
+        return Err(yt_dlp_failure(&output, &auth));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::logic(format!("Failed to parse yt-dlp output: {}", e)))
+}
+
+/// Emit a line for terminal display on the log event.
+pub(crate) fn emit_log(app: &AppHandle, job_id: &str, line: &str) {
+    let _ = app.emit(
+        EVENT_DOWNLOAD_LOG,
+        LogEvent {
+            job_id: job_id.to_string(),
+            line: line.to_string(),
+        },
+    );
+}
+
+/// Remove the partial files yt-dlp and aria2c leave behind for a destination.
+///
+/// Covers `.part`, `.ytdl`, aria2c `.aria2` control files and fragment
+/// files (`.part-Frag*`). Errors are ignored since files may not exist.
+pub(crate) fn remove_partial_files(destinations: &[PathBuf]) {
+    for destination in destinations {
+        let Some(file_name) = destination.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let dir = destination.parent().unwrap_or_else(|| Path::new("."));
+
+        for suffix in [".part", ".ytdl", ".aria2", ".part.aria2"] {
+            let _ = fs::remove_file(dir.join(format!("{}{}", file_name, suffix)));
+        }
+
+        // Fragment files: <name>.part-Frag1, <name>.part-Frag2, ...
+        let fragment_prefix = format!("{}.part-Frag", file_name);
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().starts_with(&fragment_prefix) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
 }
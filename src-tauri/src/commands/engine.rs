@@ -1,14 +1,32 @@
 //! Engine management commands.
 //!
 //! Provides commands for downloading and installing engine updates (yt-dlp, ffmpeg, aria2c).
-
-use std::fs::{self, File, OpenOptions};
-use std::io::{self, Write};
-use std::path::PathBuf;
-
-use crate::config::{DOWNLOAD_TIMEOUT_SECS, ENGINE_BINARIES};
+//! Packages are verified against a signed SHA-256 manifest before anything is installed.
+//! Also reports the installed engine versions and updates individual binaries
+//! from a remote engine index.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tauri_plugin_shell::ShellExt;
+
+use crate::commands::settings::{validate_update_url, SettingsState};
+use crate::config::{
+    EngineChannel, DOWNLOAD_TIMEOUT_SECS, ENGINE_BACKUP_DIRNAME, ENGINE_BINARIES, ENGINE_MANIFEST_SUFFIX,
+    ENGINE_PENDING_BACKUP_DIRNAME, ENGINE_SIGNATURE_SUFFIX, ENGINE_SIGNING_PUBLIC_KEY, ENGINE_STAGING_DIRNAME,
+    ENGINE_TARGET_TRIPLE, ENGINE_VERSION_FLAGS, MAX_ENGINE_DOWNLOAD_BYTES, MAX_SIGNED_DOCUMENT_BYTES, YT_DLP_SIDECAR,
+};
+#[cfg(debug_assertions)]
+use crate::config::ENV_ENGINE_SIGNING_KEY;
 use crate::error::AppError;
-use crate::utils::zip::{copy_with_retry, extract_zip, find_file_recursive};
+use crate::utils::http::{download_to_file, fetch_capped, with_file_suffix};
+use crate::utils::integrity::{sha256_file, verify_minisign, verify_sha256};
+use crate::utils::zip::{copy_with_retry, extract_zip, find_file_recursive, make_executable};
 
 /// Resolve the binaries directory with fail-safe dev/prod mode detection.
 ///
@@ -60,30 +78,385 @@ fn resolve_binaries_dir() -> Result<PathBuf, AppError> {
     }
 }
 
-/// Check if a binary file is currently locked/in use.
-fn is_file_locked(path: &PathBuf) -> bool {
+/// Whether an installed binary can be replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileAccess {
+    /// Missing or writable.
+    Free,
+    /// In use by a running process (e.g. a download).
+    Locked,
+    /// The app lacks permission to write the file.
+    PermissionDenied,
+}
+
+/// OS error codes meaning a file is held open by another process.
+#[cfg(windows)]
+const LOCKED_OS_ERRORS: &[i32] = &[
+    32, // ERROR_SHARING_VIOLATION
+    33, // ERROR_LOCK_VIOLATION
+];
+#[cfg(unix)]
+const LOCKED_OS_ERRORS: &[i32] = &[
+    26, // ETXTBSY: the executable is running
+];
+#[cfg(not(any(windows, unix)))]
+const LOCKED_OS_ERRORS: &[i32] = &[];
+
+/// Check whether a binary file is locked/in use or not writable.
+fn file_access(path: &Path) -> FileAccess {
     if !path.exists() {
-        return false;
+        return FileAccess::Free;
     }
 
     // Try to open the file with exclusive write access
     match OpenOptions::new().write(true).open(path) {
-        Ok(_) => false,
-        Err(e) => {
-            matches!(e.kind(), io::ErrorKind::PermissionDenied)
-                || e.raw_os_error() == Some(32) // ERROR_SHARING_VIOLATION on Windows
-                || e.raw_os_error() == Some(33) // ERROR_LOCK_VIOLATION on Windows
+        Ok(_) => FileAccess::Free,
+        Err(e) => open_error_access(&e),
+    }
+}
+
+/// Classify the error from opening a binary for writing.
+///
+/// Linux 6.11 and later no longer report ETXTBSY for running executables,
+/// so a running binary may also open successfully.
+fn open_error_access(error: &io::Error) -> FileAccess {
+    if error.raw_os_error().is_some_and(|code| LOCKED_OS_ERRORS.contains(&code)) {
+        FileAccess::Locked
+    } else if error.kind() == io::ErrorKind::PermissionDenied {
+        FileAccess::PermissionDenied
+    } else {
+        FileAccess::Free
+    }
+}
+
+/// Check whether a file has execute permission.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Check whether a file is an executable image (Windows runs any `.exe`).
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Refuse to touch the engine while any binary is in use or not writable.
+fn ensure_binaries_unlocked(binaries_dir: &Path) -> Result<(), AppError> {
+    for binary in ENGINE_BINARIES {
+        match file_access(&binaries_dir.join(binary)) {
+            FileAccess::Free => {}
+            FileAccess::Locked => {
+                return Err(AppError::logic(format!(
+                    "Cannot update: {} is currently in use. Please stop any active downloads and try again.",
+                    binary
+                )));
+            }
+            FileAccess::PermissionDenied => {
+                return Err(AppError::logic(format!(
+                    "Cannot update: permission denied writing {}. Check the permissions of {}.",
+                    binary,
+                    binaries_dir.display()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy binaries into a fresh staging directory on the same volume as the install.
+///
+/// Staging next to the installed binaries keeps the later swap a plain rename.
+fn stage_binaries(
+    sources: &[(&'static str, PathBuf)],
+    staging_dir: &Path,
+) -> Result<Vec<(&'static str, PathBuf)>, AppError> {
+    if staging_dir.exists() {
+        fs::remove_dir_all(staging_dir)?;
+    }
+    fs::create_dir_all(staging_dir)?;
+
+    let mut staged = Vec::new();
+    for (binary_name, source) in sources {
+        let staged_path = staging_dir.join(binary_name);
+        copy_with_retry(source, &staged_path, 3)
+            .map_err(|e| AppError::logic(format!("Failed to stage {}: {}", binary_name, e)))?;
+        staged.push((*binary_name, staged_path));
+    }
+
+    Ok(staged)
+}
+
+/// A binary that has been swapped in, remembered so the swap can be undone.
+struct SwappedBinary {
+    name: &'static str,
+    staged_path: PathBuf,
+    had_previous: bool,
+}
+
+/// Atomically replace the installed binaries with staged ones.
+///
+/// Each installed binary is renamed into a pending backup directory and the
+/// staged file renamed into its place. If any rename fails, every binary
+/// swapped so far is put back, leaving the previous engine exactly as it
+/// was. On success the pending backup replaces the previous rollback point;
+/// if that fails, the new engine stays installed and a warning is returned.
+///
+/// # Returns
+/// * `Ok(SwapOutcome)` with the number of binaries installed
+/// * `Err(AppError)` after the previous engine has been restored
+fn swap_in(binaries_dir: &Path, staged: &[(&'static str, PathBuf)]) -> Result<SwapOutcome, AppError> {
+    let pending_backup = binaries_dir.join(ENGINE_PENDING_BACKUP_DIRNAME);
+    if pending_backup.exists() {
+        fs::remove_dir_all(&pending_backup)?;
+    }
+    fs::create_dir_all(&pending_backup)?;
+
+    let mut swapped: Vec<SwappedBinary> = Vec::new();
+
+    for (binary_name, staged_path) in staged {
+        let target = binaries_dir.join(binary_name);
+        let backup = pending_backup.join(binary_name);
+        let had_previous = target.exists();
+
+        if let Err(e) = replace_binary(&target, staged_path, &backup, had_previous) {
+            let restore_errors = undo_swap(binaries_dir, &pending_backup, &swapped);
+            if restore_errors.is_empty() {
+                let _ = fs::remove_dir_all(&pending_backup);
+            }
+
+            return Err(AppError::logic(if restore_errors.is_empty() {
+                format!(
+                    "Failed to install {}: {}. The previous engine has been restored.",
+                    binary_name, e
+                )
+            } else {
+                format!(
+                    "Failed to install {}: {}. Restoring the previous engine also failed: {}",
+                    binary_name,
+                    e,
+                    restore_errors.join("; ")
+                )
+            }));
         }
+
+        swapped.push(SwappedBinary {
+            name: binary_name,
+            staged_path: staged_path.clone(),
+            had_previous,
+        });
+    }
+
+    // Promote the pending backup to the rollback point. The new binaries are
+    // already in place, so a failure here only loses the rollback point
+    let warning = promote_backup(&pending_backup, &binaries_dir.join(ENGINE_BACKUP_DIRNAME))
+        .err()
+        .map(|e| format!("The previous engine could not be kept for rollback: {}", e));
+
+    Ok(SwapOutcome {
+        installed: swapped.len(),
+        warning,
+    })
+}
+
+/// Result of a completed [`swap_in`].
+struct SwapOutcome {
+    /// Number of binaries installed.
+    installed: usize,
+    /// Why the rollback point could not be updated, if it could not.
+    warning: Option<String>,
+}
+
+impl SwapOutcome {
+    /// Append the warning, if any, to a success message.
+    fn message(&self, message: String) -> String {
+        match self.warning {
+            Some(ref warning) => format!("{} Warning: {}", message, warning),
+            None => message,
+        }
+    }
+}
+
+/// Replace the rollback point with the pending backup.
+fn promote_backup(pending_backup: &Path, backup_dir: &Path) -> io::Result<()> {
+    if backup_dir.exists() {
+        fs::remove_dir_all(backup_dir)?;
     }
+    fs::rename(pending_backup, backup_dir)
+}
+
+/// Move the installed binary (if any) to `backup` and the staged one into its place.
+///
+/// If the second rename fails, the installed binary is moved back.
+fn replace_binary(target: &Path, staged_path: &Path, backup: &Path, had_previous: bool) -> io::Result<()> {
+    if had_previous {
+        fs::rename(target, backup)?;
+    }
+
+    if let Err(e) = fs::rename(staged_path, target) {
+        if had_previous {
+            let _ = fs::rename(backup, target);
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Reverse a partial swap, newest first.
+///
+/// New binaries go back to their staging paths (so a rollback's source is
+/// never lost) and the backed-up binaries return to their original paths.
+/// Returns a description of every file that could not be restored.
+fn undo_swap(binaries_dir: &Path, backup_dir: &Path, swapped: &[SwappedBinary]) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for binary in swapped.iter().rev() {
+        let target = binaries_dir.join(binary.name);
+
+        if let Err(e) = fs::rename(&target, &binary.staged_path) {
+            errors.push(format!("{}: {}", binary.name, e));
+            continue;
+        }
+        if binary.had_previous {
+            if let Err(e) = fs::rename(backup_dir.join(binary.name), &target) {
+                errors.push(format!("{}: {}", binary.name, e));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checksum manifest published alongside an engine package.
+#[derive(Debug, Deserialize)]
+struct EngineManifest {
+    /// SHA-256 of the ZIP archive.
+    archive_sha256: String,
+    /// SHA-256 of each binary in the archive, keyed by file name.
+    binaries: HashMap<String, String>,
+}
+
+/// Build the HTTP client used for engine downloads, with the network settings applied.
+fn http_client(settings: &SettingsState) -> Result<reqwest::Client, AppError> {
+    settings
+        .network()
+        .http_client(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+}
+
+/// The minisign public key engine documents are verified with, if any.
+///
+/// Debug builds take a replacement key from `ENV_ENGINE_SIGNING_KEY`, so a
+/// local mirror can be signed with a development key. Release builds only
+/// use the key embedded at build time.
+#[cfg(debug_assertions)]
+fn signing_public_key() -> Option<String> {
+    std::env::var(ENV_ENGINE_SIGNING_KEY)
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+        .or_else(|| ENGINE_SIGNING_PUBLIC_KEY.map(str::to_string))
+}
+
+#[cfg(not(debug_assertions))]
+fn signing_public_key() -> Option<String> {
+    ENGINE_SIGNING_PUBLIC_KEY.map(str::to_string)
+}
+
+/// Report whether this build can install engine updates.
+///
+/// Engine documents must be signed, so updates are disabled in builds
+/// that embed no engine signing key.
+#[tauri::command]
+pub fn engine_updates_enabled() -> bool {
+    signing_public_key().is_some()
+}
+
+/// Fetch a JSON document and verify its minisign signature (`<url>.minisig`).
+///
+/// A missing or invalid signature rejects the document, as does a document
+/// or signature over `MAX_SIGNED_DOCUMENT_BYTES`.
+async fn fetch_signed<T: DeserializeOwned>(client: &reqwest::Client, url: &str, label: &str) -> Result<T, AppError> {
+    let public_key = signing_public_key()
+        .ok_or_else(|| AppError::logic("Engine updates are disabled: this build has no engine signing key."))?;
+
+    let signature_url = with_file_suffix(url, ENGINE_SIGNATURE_SUFFIX)?;
+
+    let document = fetch_capped(client, url, MAX_SIGNED_DOCUMENT_BYTES)
+        .await
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Could not fetch the {}: {}", label, e)))?;
+
+    let signature = fetch_capped(client, &signature_url, MAX_SIGNED_DOCUMENT_BYTES)
+        .await
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Could not fetch the {} signature: {}", label, e)))?;
+    let signature = String::from_utf8(signature)
+        .map_err(|_| AppError::IntegrityCheckFailed(format!("Invalid {} signature: not UTF-8", label)))?;
+
+    verify_minisign(&document, &signature, &public_key)?;
+
+    serde_json::from_slice(&document)
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Invalid {}: {}", label, e)))
+}
+
+/// Locate each engine binary in the extracted package and check it against the manifest.
+///
+/// Every binary present must be listed, and every listed binary must be
+/// present, so a package cannot smuggle in or silently drop an engine.
+fn verify_binaries(manifest: &EngineManifest, extract_dir: &Path) -> Result<Vec<(&'static str, PathBuf)>, AppError> {
+    let mut verified = Vec::new();
+
+    for binary_name in ENGINE_BINARIES {
+        let source = find_file_recursive(extract_dir, binary_name);
+        let expected = manifest.binaries.get(*binary_name);
+
+        match (source, expected) {
+            (Some(source), Some(expected)) => {
+                verify_sha256(&source, expected, binary_name)?;
+                verified.push((*binary_name, source));
+            }
+            (Some(_), None) => {
+                return Err(AppError::IntegrityCheckFailed(format!(
+                    "{} is not listed in the engine manifest",
+                    binary_name
+                )));
+            }
+            (None, Some(_)) => {
+                return Err(AppError::IntegrityCheckFailed(format!(
+                    "{} is listed in the engine manifest but missing from the package",
+                    binary_name
+                )));
+            }
+            (None, None) => {}
+        }
+    }
+
+    Ok(verified)
 }
 
 /// Download and install engine update from a remote ZIP file.
 ///
 /// This command is self-healing: if binaries are missing or corrupted,
-/// it will download fresh copies from the specified URL.
+/// it will download fresh copies from the configured engine package URL.
+///
+/// Nothing is installed unless the package verifies against its manifest
+/// (`<url>.manifest.json`): the archive and every binary must match the
+/// listed SHA-256, and the manifest must carry a valid minisign signature
+/// from `ENGINE_SIGNING_PUBLIC_KEY`.
+///
+/// The new binaries are swapped in all at once; if any of them cannot be
+/// installed, the previous engine is restored. The replaced binaries are
+/// kept for `rollback_engine`.
 #[tauri::command]
-pub async fn install_engine_update(url: String) -> Result<String, AppError> {
-    // Step 0: Validate URL
+pub async fn install_engine_update(app: AppHandle, settings: State<'_, SettingsState>) -> Result<String, AppError> {
+    // Step 0: Resolve and validate the package URL
+    let url = settings.update_sources().engine_package_url;
     if url.is_empty() {
         return Err(AppError::logic("No update URL provided."));
     }
@@ -97,104 +470,468 @@ pub async fn install_engine_update(url: String) -> Result<String, AppError> {
     }
 
     // Step 2: Check if any binaries are currently in use
-    for binary in ENGINE_BINARIES {
-        let binary_path = binaries_dir.join(binary);
-        if is_file_locked(&binary_path) {
-            return Err(AppError::logic(format!(
-                "Cannot update: {} is currently in use. Please stop any active downloads and try again.",
-                binary
-            )));
-        }
-    }
+    ensure_binaries_unlocked(&binaries_dir)?;
+
+    // Step 3: Fetch and verify the checksum manifest
+    let client = http_client(&settings)?;
 
-    // Step 3: Download the ZIP file to a temporary location
+    let manifest_url = with_file_suffix(&url, ENGINE_MANIFEST_SUFFIX)?;
+    let manifest: EngineManifest = fetch_signed(&client, &manifest_url, "engine manifest").await?;
+
+    // Step 4: Download the ZIP file to a temporary location
     let temp_dir = std::env::temp_dir().join("godspeed_engine_update");
 
-    // Clean up any previous failed attempts
-    if temp_dir.exists() {
-        let _ = fs::remove_dir_all(&temp_dir);
+    // Clean up extracted files from previous attempts; a partial download is kept and resumed
+    let extract_dir = temp_dir.join("extracted");
+    if extract_dir.exists() {
+        let _ = fs::remove_dir_all(&extract_dir);
     }
 
     fs::create_dir_all(&temp_dir)?;
 
     let zip_path = temp_dir.join("engine.zip");
+    download_to_file(&app, &client, &url, &zip_path, MAX_ENGINE_DOWNLOAD_BYTES, "engine").await?;
+
+    if let Err(e) = verify_sha256(&zip_path, &manifest.archive_sha256, "engine package") {
+        let _ = fs::remove_file(&zip_path);
+        return Err(e);
+    }
 
-    // Download using async reqwest
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| AppError::logic(format!("Failed to create HTTP client: {}", e)))?;
+    // Step 5: Extract the ZIP file and verify every binary before touching the installed ones
+    extract_zip(&zip_path, &extract_dir)?;
 
-    let response = client.get(&url).send().await?;
+    let verified = verify_binaries(&manifest, &extract_dir)?;
 
-    if !response.status().is_success() {
-        return Err(AppError::logic(format!(
-            "Download failed with status: {} - {}",
-            response.status(),
-            response
-                .status()
-                .canonical_reason()
-                .unwrap_or("Unknown error")
-        )));
+    if verified.is_empty() {
+        let _ = fs::remove_dir_all(&temp_dir);
+        return Err(AppError::logic(
+            "No engine binaries found in the update package.",
+        ));
     }
 
-    let bytes = response.bytes().await?;
+    // Step 6: Stage the verified binaries next to the installed ones, then swap them in
+    let staging_dir = binaries_dir.join(ENGINE_STAGING_DIRNAME);
+    let staged = stage_binaries(&verified, &staging_dir);
+    let installed = staged.and_then(|staged| swap_in(&binaries_dir, &staged));
 
-    let mut zip_file =
-        File::create(&zip_path).map_err(|e| AppError::logic(format!("Failed to create temp ZIP file: {}", e)))?;
+    // Step 7: Cleanup temp files
+    let _ = fs::remove_dir_all(&temp_dir);
+    let _ = fs::remove_dir_all(&staging_dir);
 
-    zip_file
-        .write_all(&bytes)
-        .map_err(|e| AppError::logic(format!("Failed to write ZIP file: {}", e)))?;
+    // Step 8: Return result
+    let outcome = installed?;
 
-    drop(zip_file);
+    Ok(outcome.message(format!(
+        "Engine V12 updated successfully! {} binaries installed.",
+        outcome.installed
+    )))
+}
 
-    // Step 4: Extract the ZIP file
-    let extract_dir = temp_dir.join("extracted");
-    extract_zip(&zip_path, &extract_dir)?;
+/// Restore the engine that was replaced by the last update.
+///
+/// The current binaries become the new rollback point, so rolling back
+/// twice returns to the updated engine.
+#[tauri::command]
+pub async fn rollback_engine() -> Result<String, AppError> {
+    let binaries_dir = resolve_binaries_dir()?;
+    let backup_dir = binaries_dir.join(ENGINE_BACKUP_DIRNAME);
 
-    // Step 5: Copy extracted binaries to the target directory
-    let mut updated_count = 0;
-    let mut errors: Vec<String> = Vec::new();
+    let has_backup = ENGINE_BINARIES
+        .iter()
+        .any(|binary| backup_dir.join(binary).is_file());
+    if !has_backup {
+        return Err(AppError::logic("No previous engine to roll back to."));
+    }
+
+    ensure_binaries_unlocked(&binaries_dir)?;
+
+    // Move the backup out of the way so the swap can write a fresh one
+    let staging_dir = binaries_dir.join(ENGINE_STAGING_DIRNAME);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::rename(&backup_dir, &staging_dir)?;
+
+    let staged: Vec<(&'static str, PathBuf)> = ENGINE_BINARIES
+        .iter()
+        .map(|binary| (*binary, staging_dir.join(binary)))
+        .filter(|(_, path)| path.is_file())
+        .collect();
+
+    match swap_in(&binaries_dir, &staged) {
+        Ok(outcome) => {
+            let _ = fs::remove_dir_all(&staging_dir);
+            Ok(outcome.message(format!(
+                "Engine rolled back successfully! {} binaries restored.",
+                outcome.installed
+            )))
+        }
+        Err(e) => {
+            // The swap put the staged binaries back; keep them as the rollback point
+            let _ = fs::rename(&staging_dir, &backup_dir);
+            Err(e)
+        }
+    }
+}
+
+/// Installation status of a single engine binary.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineBinaryStatus {
+    /// File name, as listed in `ENGINE_BINARIES`.
+    pub name: String,
+    pub path: String,
+    pub present: bool,
+    pub executable: bool,
+    /// Whether the file is in use (e.g. by a running download) or not writable.
+    pub access: FileAccess,
+    pub size_bytes: Option<u64>,
+    pub sha256: Option<String>,
+    /// Version reported by the binary itself.
+    pub version: Option<String>,
+    /// Why the version could not be determined, if it could not.
+    pub error: Option<String>,
+}
+
+/// Installation status of the whole engine.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineStatus {
+    pub binaries_dir: String,
+    pub binaries: Vec<EngineBinaryStatus>,
+}
+
+/// Extract the version from the first line of `--version` output.
+///
+/// Handles both bare versions (`2024.08.06`) and `name version X ...`
+/// banners (`ffmpeg version 6.1.1-static https://...`).
+fn parse_version(output: &str) -> Option<String> {
+    let first_line = output.lines().map(str::trim).find(|line| !line.is_empty())?;
+
+    let mut words = first_line.split_whitespace();
+    match words.position(|word| word == "version") {
+        Some(_) => words.next().map(str::to_string),
+        None => Some(first_line.to_string()),
+    }
+}
+
+/// Run an engine binary with its version flag and parse the reported version.
+async fn query_version(app: &AppHandle, name: &str, path: &Path) -> Result<String, String> {
+    let flag = ENGINE_VERSION_FLAGS
+        .iter()
+        .find(|(sidecar, _)| name.starts_with(sidecar))
+        .map(|(_, flag)| *flag)
+        .ok_or_else(|| format!("No version flag known for {}", name))?;
+
+    let output = app
+        .shell()
+        .command(path)
+        .args([flag])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", name, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} {} exited with code {}",
+            name,
+            flag,
+            output.status.code().unwrap_or(-1)
+        ));
+    }
+
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("{} printed no version", name))
+}
+
+/// Inspect a single installed engine binary.
+async fn binary_status(app: &AppHandle, binaries_dir: &Path, binary_name: &str) -> EngineBinaryStatus {
+    let path = binaries_dir.join(binary_name);
+    let present = path.is_file();
+    let executable = is_executable(&path);
+
+    let (version, error) = if !present {
+        (None, Some(format!("{} is not installed", binary_name)))
+    } else if !executable {
+        (None, Some(format!("{} is not executable", binary_name)))
+    } else {
+        match query_version(app, binary_name, &path).await {
+            Ok(version) => (Some(version), None),
+            Err(e) => (None, Some(e)),
+        }
+    };
+
+    EngineBinaryStatus {
+        name: binary_name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        present,
+        executable,
+        access: file_access(&path),
+        size_bytes: fs::metadata(&path).map(|meta| meta.len()).ok(),
+        sha256: present.then(|| sha256_file(&path).ok()).flatten(),
+        version,
+        error,
+    }
+}
+
+/// Report the installed engine binaries: presence, permissions, lock state,
+/// size, SHA-256 and the version each binary reports.
+#[tauri::command]
+pub async fn get_engine_status(app: AppHandle) -> Result<EngineStatus, AppError> {
+    let binaries_dir = resolve_binaries_dir()?;
+    let mut binaries = Vec::new();
 
     for binary_name in ENGINE_BINARIES {
-        let target_path = binaries_dir.join(binary_name);
+        binaries.push(binary_status(&app, &binaries_dir, binary_name).await);
+    }
+
+    Ok(EngineStatus {
+        binaries_dir: binaries_dir.to_string_lossy().to_string(),
+        binaries,
+    })
+}
 
-        // Search for the binary in extracted contents
-        let source_path = find_file_recursive(&extract_dir, binary_name);
+/// Remote index of the latest individual engine binaries.
+///
+/// Signed like the engine manifest (`<url>.minisig`): the SHA-256 entries
+/// are only trusted because the signature covers them, never on their own.
+#[derive(Debug, Deserialize)]
+struct EngineIndex {
+    binaries: Vec<EngineIndexEntry>,
+}
 
-        if let Some(source) = source_path {
-            match copy_with_retry(&source, &target_path, 3) {
-                Ok(_) => {
-                    updated_count += 1;
-                }
-                Err(e) => {
-                    errors.push(format!("{}: {}", binary_name, e));
-                }
-            }
+/// A single binary build in the engine index.
+#[derive(Debug, Clone, Deserialize)]
+struct EngineIndexEntry {
+    /// Sidecar name, e.g. `yt-dlp`.
+    name: String,
+    #[serde(default)]
+    channel: EngineChannel,
+    version: String,
+    /// Target triple the binary was built for, e.g. `x86_64-pc-windows-msvc`.
+    target: String,
+    url: String,
+    sha256: String,
+}
+
+/// Update state of a single engine binary.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineBinaryUpdate {
+    /// File name, as listed in `ENGINE_BINARIES`.
+    pub name: String,
+    pub channel: EngineChannel,
+    pub installed_version: Option<String>,
+    /// `None` if the index has no build of this binary for this platform.
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+}
+
+/// Response structure for `check_engine_update`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineUpdateCheck {
+    pub update_available: bool,
+    pub binaries: Vec<EngineBinaryUpdate>,
+}
+
+/// An engine binary together with the index entry it would be updated from.
+struct PlannedUpdate {
+    binary_name: &'static str,
+    entry: Option<EngineIndexEntry>,
+    update: EngineBinaryUpdate,
+}
+
+/// Compare the installed binaries with the engine index.
+///
+/// The index is rejected unless it carries a valid signature from
+/// `ENGINE_SIGNING_PUBLIC_KEY`. yt-dlp follows `yt_dlp_channel`; the other
+/// engines always use `Stable`. A binary is up to date when its version or
+/// its SHA-256 matches the index entry, so switching channels counts as an
+/// update.
+async fn plan_engine_update(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    index_url: &str,
+    yt_dlp_channel: EngineChannel,
+) -> Result<Vec<PlannedUpdate>, AppError> {
+    let index: EngineIndex = fetch_signed(client, index_url, "engine index").await?;
+    let binaries_dir = resolve_binaries_dir()?;
+
+    let mut plan = Vec::new();
+
+    for &binary_name in ENGINE_BINARIES {
+        let channel = if binary_name.starts_with(YT_DLP_SIDECAR) {
+            yt_dlp_channel
+        } else {
+            EngineChannel::Stable
+        };
+
+        let entry = index
+            .binaries
+            .iter()
+            .find(|entry| {
+                binary_name.starts_with(entry.name.as_str())
+                    && entry.channel == channel
+                    && entry.target == ENGINE_TARGET_TRIPLE
+            })
+            .cloned();
+
+        let status = binary_status(app, &binaries_dir, binary_name).await;
+
+        let update_available = entry.as_ref().is_some_and(|entry| {
+            let same_version = status.version.as_deref() == Some(entry.version.as_str());
+            let same_hash = status
+                .sha256
+                .as_deref()
+                .is_some_and(|hash| hash.eq_ignore_ascii_case(&entry.sha256));
+            !same_version && !same_hash
+        });
+
+        plan.push(PlannedUpdate {
+            binary_name,
+            update: EngineBinaryUpdate {
+                name: binary_name.to_string(),
+                channel,
+                installed_version: status.version,
+                latest_version: entry.as_ref().map(|entry| entry.version.clone()),
+                update_available,
+            },
+            entry,
+        });
+    }
+
+    Ok(plan)
+}
+
+/// Check the signed engine index for newer builds of the installed binaries.
+#[tauri::command]
+pub async fn check_engine_update(
+    app: AppHandle,
+    settings: State<'_, SettingsState>,
+) -> Result<EngineUpdateCheck, AppError> {
+    let client = http_client(&settings)?;
+    let index_url = settings.update_sources().engine_index_url;
+    let plan = plan_engine_update(&app, &client, &index_url, settings.current().yt_dlp_channel).await?;
+
+    let binaries: Vec<EngineBinaryUpdate> = plan.into_iter().map(|planned| planned.update).collect();
+
+    Ok(EngineUpdateCheck {
+        update_available: binaries.iter().any(|binary| binary.update_available),
+        binaries,
+    })
+}
+
+/// Download and install only the engine binaries that have a newer build in the index.
+///
+/// The index must be signed, and its download URLs must use https as the
+/// update sources do. Each binary is verified against the SHA-256 listed in
+/// the signed index, then all of them are swapped in together like
+/// `install_engine_update`.
+#[tauri::command]
+pub async fn update_engine(app: AppHandle, settings: State<'_, SettingsState>) -> Result<String, AppError> {
+    let client = http_client(&settings)?;
+    let index_url = settings.update_sources().engine_index_url;
+    let plan = plan_engine_update(&app, &client, &index_url, settings.current().yt_dlp_channel).await?;
+
+    let pending: Vec<(&'static str, EngineIndexEntry)> = plan
+        .into_iter()
+        .filter(|planned| planned.update.update_available)
+        .filter_map(|planned| planned.entry.map(|entry| (planned.binary_name, entry)))
+        .collect();
+
+    if pending.is_empty() {
+        return Ok("Engine is up to date.".to_string());
+    }
+
+    // Index entries get the same https check as the configured update URLs
+    for (binary_name, entry) in &pending {
+        validate_update_url(&format!("{} download", binary_name), &entry.url)?;
+    }
+
+    let binaries_dir = resolve_binaries_dir()?;
+    ensure_binaries_unlocked(&binaries_dir)?;
+
+    // Partial downloads from an interrupted update are kept and resumed
+    let temp_dir = std::env::temp_dir().join("godspeed_engine_binaries");
+    fs::create_dir_all(&temp_dir)?;
+
+    let mut downloaded = Vec::new();
+    for (binary_name, entry) in &pending {
+        let path = temp_dir.join(binary_name);
+        let download_id = format!("engine:{}", binary_name);
+        download_to_file(&app, &client, &entry.url, &path, MAX_ENGINE_DOWNLOAD_BYTES, &download_id).await?;
+
+        if let Err(e) = verify_sha256(&path, &entry.sha256, binary_name) {
+            let _ = fs::remove_file(&path);
+            return Err(e);
         }
+        make_executable(&path)?;
+        downloaded.push((*binary_name, path));
     }
 
-    // Step 6: Cleanup temp files
+    let staging_dir = binaries_dir.join(ENGINE_STAGING_DIRNAME);
+    let installed = stage_binaries(&downloaded, &staging_dir).and_then(|staged| swap_in(&binaries_dir, &staged));
+
     let _ = fs::remove_dir_all(&temp_dir);
+    let _ = fs::remove_dir_all(&staging_dir);
+
+    let summary: Vec<String> = pending
+        .iter()
+        .map(|(binary_name, entry)| format!("{} {}", binary_name, entry.version))
+        .collect();
+
+    let outcome = installed?;
+
+    Ok(outcome.message(format!(
+        "Engine updated successfully! {} binaries installed: {}",
+        outcome.installed,
+        summary.join(", ")
+    )))
+}
 
-    // Step 7: Return result
-    if !errors.is_empty() {
-        return Err(AppError::logic(format!(
-            "Updated {} binaries, but some failed: {}",
-            updated_count,
-            errors.join("; ")
-        )));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_and_writable_files_are_free() {
+        let dir = std::env::temp_dir().join(format!("godspeed-access-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("yt-dlp");
+        fs::write(&file, b"binary").unwrap();
+
+        assert_eq!(file_access(&dir.join("missing")), FileAccess::Free);
+        assert_eq!(file_access(&file), FileAccess::Free);
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    if updated_count == 0 {
-        return Err(AppError::logic(
-            "No engine binaries found in the update package.",
-        ));
+    #[test]
+    fn failed_backup_promotion_keeps_the_new_engine() {
+        let dir = std::env::temp_dir().join(format!("godspeed-swap-{}", uuid::Uuid::new_v4()));
+        let staging = dir.join(ENGINE_STAGING_DIRNAME);
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(dir.join("yt-dlp"), b"old").unwrap();
+        fs::write(staging.join("yt-dlp"), b"new").unwrap();
+        // A file where the rollback directory belongs cannot be replaced by a directory
+        fs::write(dir.join(ENGINE_BACKUP_DIRNAME), b"").unwrap();
+
+        let outcome = swap_in(&dir, &[("yt-dlp", staging.join("yt-dlp"))]).unwrap();
+
+        assert_eq!(outcome.installed, 1);
+        assert!(outcome.warning.is_some());
+        assert_eq!(fs::read(dir.join("yt-dlp")).unwrap(), b"new");
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 
-    Ok(format!(
-        "Engine V12 updated successfully! {} binaries installed.",
-        updated_count
-    ))
+    #[test]
+    fn classifies_open_errors() {
+        for code in LOCKED_OS_ERRORS {
+            assert_eq!(open_error_access(&io::Error::from_raw_os_error(*code)), FileAccess::Locked);
+        }
+
+        assert_eq!(
+            open_error_access(&io::Error::from(io::ErrorKind::PermissionDenied)),
+            FileAccess::PermissionDenied
+        );
+        assert_eq!(open_error_access(&io::Error::from(io::ErrorKind::NotFound)), FileAccess::Free);
+    }
 }
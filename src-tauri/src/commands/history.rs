@@ -0,0 +1,286 @@
+//! Download history commands.
+//!
+//! Every completed download is appended to a JSON Lines log in the app data
+//! directory. Deletions are appended as tombstones, so the log is never
+//! rewritten and a crash can at worst lose the last line.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::commands::downloader::{DownloadRequest, DuplicatePolicy};
+use crate::commands::queue;
+use crate::config::HISTORY_FILENAME;
+use crate::error::AppError;
+use crate::utils::paths::app_data_path;
+use crate::utils::progress::FinishedFile;
+
+/// Tauri-managed lock serializing history writes from concurrent download workers.
+#[derive(Default)]
+pub struct HistoryState(Mutex<()>);
+
+impl HistoryState {
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A completed download.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: String,
+    /// The request that produced the file: source URL, format and output settings.
+    pub request: DownloadRequest,
+    /// Title as resolved by yt-dlp.
+    pub title: Option<String>,
+    /// Uploader or channel name as resolved by yt-dlp.
+    #[serde(default)]
+    pub uploader: Option<String>,
+    pub file_path: String,
+    pub size_bytes: Option<u64>,
+    /// Duration in seconds.
+    pub duration: Option<f64>,
+    /// Completion time in seconds since the Unix epoch.
+    pub completed_at: u64,
+    /// Set when listing if the file is no longer on disk.
+    #[serde(default, skip_deserializing)]
+    pub file_missing: bool,
+}
+
+/// A single line of the history log.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum HistoryRecord {
+    Add(Box<HistoryEntry>),
+    Delete { id: String },
+}
+
+/// Resolve the history log in the app data directory.
+fn history_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app_data_path(app, HISTORY_FILENAME)
+}
+
+/// Append a record to the history log.
+fn append_record(app: &AppHandle, record: &HistoryRecord) -> Result<(), AppError> {
+    let path = history_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_string(record)
+        .map_err(|e| AppError::logic(format!("Failed to serialize history entry: {}", e)))?;
+    line.push('\n');
+
+    let state = app.state::<HistoryState>();
+    let _guard = state.lock();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(line.as_bytes())?;
+
+    Ok(())
+}
+
+/// Replay the history log, newest entry first.
+///
+/// Unreadable lines (e.g. a torn final write) are skipped.
+fn load_entries(app: &AppHandle) -> Result<Vec<HistoryEntry>, AppError> {
+    let path = history_file_path(app)?;
+    let content = {
+        let state = app.state::<HistoryState>();
+        let _guard = state.lock();
+        match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    let mut entries = replay(&content);
+    for entry in &mut entries {
+        entry.file_missing = !Path::new(&entry.file_path).is_file();
+    }
+
+    Ok(entries)
+}
+
+/// Apply the records of a history log in order, newest entry first.
+fn replay(content: &str) -> Vec<HistoryEntry> {
+    let mut entries: Vec<HistoryEntry> = Vec::new();
+    for record in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<HistoryRecord>(line).ok())
+    {
+        match record {
+            HistoryRecord::Add(entry) => entries.push(*entry),
+            HistoryRecord::Delete { id } => entries.retain(|entry| entry.id != id),
+        }
+    }
+
+    entries.reverse();
+    entries
+}
+
+/// Whether the title, uploader, source URL or file path contains `query`.
+///
+/// `query` must already be lowercase.
+fn matches_query(entry: &HistoryEntry, query: &str) -> bool {
+    [entry.title.as_deref(), entry.uploader.as_deref()]
+        .into_iter()
+        .flatten()
+        .chain([entry.request.url.as_str(), entry.file_path.as_str()])
+        .any(|field| field.to_lowercase().contains(query))
+}
+
+/// Record a completed download in the history log.
+pub fn record(app: &AppHandle, request: &DownloadRequest, file: &FinishedFile) -> Result<(), AppError> {
+    let completed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let entry = HistoryEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        request: request.clone(),
+        title: file.title.clone(),
+        uploader: file.uploader.clone(),
+        file_path: file.filepath.clone(),
+        size_bytes: fs::metadata(&file.filepath).map(|meta| meta.len()).ok(),
+        duration: file.duration,
+        completed_at,
+        file_missing: false,
+    };
+
+    append_record(app, &HistoryRecord::Add(Box::new(entry)))
+}
+
+/// List the download history, newest first.
+///
+/// Entries whose file was moved or deleted have `file_missing` set.
+#[tauri::command]
+pub async fn list_history(app: AppHandle) -> Result<Vec<HistoryEntry>, AppError> {
+    load_entries(&app)
+}
+
+/// Search the history by title, uploader, source URL or file path (case-insensitive).
+#[tauri::command]
+pub async fn search_history(app: AppHandle, query: String) -> Result<Vec<HistoryEntry>, AppError> {
+    let query = query.trim().to_lowercase();
+
+    let mut entries = load_entries(&app)?;
+    entries.retain(|entry| matches_query(entry, &query));
+
+    Ok(entries)
+}
+
+/// Remove an entry from the history. The downloaded file is left untouched.
+#[tauri::command]
+pub async fn delete_history_entry(app: AppHandle, id: String) -> Result<(), AppError> {
+    if !load_entries(&app)?.iter().any(|entry| entry.id == id) {
+        return Err(AppError::logic(format!("No history entry with ID: {}", id)));
+    }
+
+    append_record(&app, &HistoryRecord::Delete { id })
+}
+
+/// Download a history entry again with its original settings.
+///
+/// The item is already in the download archive, so the job overwrites any
+/// existing file instead of being skipped. The job goes through the
+/// download queue; returns its job ID.
+#[tauri::command]
+pub async fn redownload_history_entry(app: AppHandle, id: String) -> Result<String, AppError> {
+    let entry = load_entries(&app)?
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| AppError::logic(format!("No history entry with ID: {}", id)))?;
+
+    let request = DownloadRequest {
+        duplicates: DuplicatePolicy::Overwrite,
+        ..entry.request
+    };
+
+    let job_ids = queue::enqueue(&app, vec![request])?;
+    job_ids
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::logic("Failed to enqueue download."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(id: &str, title: &str) -> String {
+        serde_json::json!({
+            "op": "add",
+            "id": id,
+            "request": {
+                "url": format!("https://www.youtube.com/watch?v={}", id),
+                "output_path": "/music",
+                "mode": { "mode": "audio", "format": { "format": "best" } }
+            },
+            "title": title,
+            "uploader": "Some Channel",
+            "file_path": format!("/music/{}.opus", title),
+            "size_bytes": 1024,
+            "duration": 61.0,
+            "completed_at": 1_700_000_000
+        })
+        .to_string()
+    }
+
+    fn delete(id: &str) -> String {
+        serde_json::json!({ "op": "delete", "id": id }).to_string()
+    }
+
+    fn ids(entries: &[HistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.id.as_str()).collect()
+    }
+
+    #[test]
+    fn replays_adds_and_tombstones() {
+        let log = [add("a", "First"), add("b", "Second"), delete("a"), add("c", "Third")].join("\n");
+        assert_eq!(ids(&replay(&log)), ["c", "b"]);
+
+        // An entry added again after its tombstone is listed again
+        let log = [log, add("a", "First again")].join("\n");
+        let entries = replay(&log);
+        assert_eq!(ids(&entries), ["a", "c", "b"]);
+        assert_eq!(entries[0].title.as_deref(), Some("First again"));
+    }
+
+    #[test]
+    fn skips_torn_and_garbage_lines() {
+        let torn = add("c", "Third");
+        let log = format!(
+            "{}\nnot json\n{}\n\n{}",
+            add("a", "First"),
+            add("b", "Second"),
+            &torn[..torn.len() / 2]
+        );
+
+        let entries = replay(&log);
+        assert_eq!(ids(&entries), ["b", "a"]);
+        assert!(entries.iter().all(|entry| !entry.file_missing));
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let entries = replay(&add("dQw4w9WgXcQ", "Never Gonna Give You Up"));
+        let entry = &entries[0];
+
+        for query in ["never gonna", "GIVE YOU", "some channel", "dqw4w9wgxcq", "music/never"] {
+            assert!(matches_query(entry, &query.to_lowercase()), "missed {:?}", query);
+        }
+        for query in ["let you down", "other channel", "vimeo.com"] {
+            assert!(!matches_query(entry, query), "matched {:?}", query);
+        }
+    }
+}
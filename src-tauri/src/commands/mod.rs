@@ -3,6 +3,13 @@
 //! Re-exports all Tauri commands for easy registration in lib.rs.
 
 pub mod app_update;
+pub mod auth;
+pub mod control;
 pub mod downloader;
 pub mod engine;
 pub mod files;
+pub mod history;
+pub mod playlist;
+pub mod probe;
+pub mod queue;
+pub mod settings;
@@ -0,0 +1,257 @@
+//! Playlist and channel commands.
+//!
+//! Expands playlist/channel URLs into their entries and enqueues the
+//! selected entries as individual download jobs.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::commands::downloader::{run_yt_dlp_json, DownloadRequest, PlaylistItem};
+use crate::commands::queue;
+use crate::commands::settings::SettingsState;
+use crate::error::AppError;
+use crate::utils::validation::normalize_url;
+
+/// A single entry of an expanded playlist.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistEntry {
+    /// 1-based index of the entry within the playlist.
+    pub index: u32,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub duration: Option<f64>,
+}
+
+/// Response structure for an expanded playlist or channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistInfo {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub uploader: Option<String>,
+    pub entries: Vec<PlaylistEntry>,
+}
+
+/// yt-dlp `--flat-playlist -J` output structures.
+#[derive(Deserialize)]
+struct FlatPlaylist {
+    #[serde(rename = "_type")]
+    kind: Option<String>,
+    id: Option<String>,
+    title: Option<String>,
+    uploader: Option<String>,
+    channel: Option<String>,
+    #[serde(default)]
+    entries: Vec<FlatEntry>,
+}
+
+#[derive(Deserialize)]
+struct FlatEntry {
+    #[serde(rename = "_type")]
+    kind: Option<String>,
+    id: Option<String>,
+    title: Option<String>,
+    url: Option<String>,
+    duration: Option<f64>,
+}
+
+/// Expand a playlist or channel URL into its entries.
+///
+/// Uses `--flat-playlist` so only the listing is fetched, not every video page.
+#[tauri::command]
+pub async fn expand_playlist(
+    app: AppHandle,
+    settings: State<'_, SettingsState>,
+    url: String,
+) -> Result<PlaylistInfo, AppError> {
+    let url = normalize_url(&url, &settings.allowed_sites())?;
+
+    let playlist: FlatPlaylist =
+        run_yt_dlp_json(&app, &["--flat-playlist", "-J", "--yes-playlist"], &url).await?;
+
+    playlist_info(playlist)
+}
+
+/// Turn a flat playlist listing into numbered entries.
+///
+/// A channel root lists its tabs (Videos, Shorts, Live) as nested
+/// playlists. Their indices would select whole tabs, so such listings are
+/// rejected in favor of a tab URL.
+fn playlist_info(playlist: FlatPlaylist) -> Result<PlaylistInfo, AppError> {
+    if playlist.kind.as_deref() != Some("playlist") {
+        return Err(AppError::logic("URL is not a playlist or channel."));
+    }
+
+    if let Some(tab) = playlist
+        .entries
+        .iter()
+        .find(|entry| entry.kind.as_deref() == Some("playlist"))
+    {
+        return Err(AppError::logic(format!(
+            "This URL lists playlists, not videos. Pick a tab or playlist URL instead, e.g. {}",
+            tab.url.as_deref().unwrap_or("the channel's Videos tab")
+        )));
+    }
+
+    let entries = playlist
+        .entries
+        .into_iter()
+        .zip(1..)
+        .map(|(entry, index)| PlaylistEntry {
+            index,
+            id: entry.id,
+            title: entry.title,
+            url: entry.url,
+            duration: entry.duration,
+        })
+        .collect();
+
+    Ok(PlaylistInfo {
+        id: playlist.id,
+        title: playlist.title,
+        uploader: playlist.uploader.or(playlist.channel),
+        entries,
+    })
+}
+
+/// Enqueue selected entries of a playlist as individual download jobs.
+///
+/// `request` carries the playlist URL and the download settings shared by
+/// every entry. `selection` uses yt-dlp style index ranges, e.g. `"1-5,8,12-"`.
+/// An empty selection picks every entry. With `numbered` set, filenames
+/// are prefixed with `%(playlist_index)s`.
+#[tauri::command]
+pub async fn enqueue_playlist(
+    app: AppHandle,
+    request: DownloadRequest,
+    selection: String,
+    entry_count: u32,
+    numbered: bool,
+) -> Result<Vec<String>, AppError> {
+    let indices = parse_index_ranges(&selection, entry_count)?;
+
+    let requests: Vec<DownloadRequest> = indices
+        .into_iter()
+        .map(|index| DownloadRequest {
+            playlist: Some(PlaylistItem { index, numbered }),
+            ..request.clone()
+        })
+        .collect();
+
+    queue::enqueue(&app, requests)
+}
+
+/// Parse index ranges such as `"1-5,8,12-"` into sorted, unique 1-based indices.
+///
+/// Open-ended ranges run to `count`. Indices outside `1..=count` are rejected.
+fn parse_index_ranges(selection: &str, count: u32) -> Result<Vec<u32>, AppError> {
+    if selection.trim().is_empty() {
+        return Ok((1..=count).collect());
+    }
+
+    let parse_index = |value: &str| -> Result<u32, AppError> {
+        let index: u32 = value
+            .trim()
+            .parse()
+            .map_err(|_| AppError::logic(format!("Invalid playlist index: {}", value.trim())))?;
+
+        if index == 0 || index > count {
+            return Err(AppError::logic(format!(
+                "Playlist index {} is out of range (1-{}).",
+                index, count
+            )));
+        }
+        Ok(index)
+    };
+
+    let mut indices = Vec::new();
+
+    for part in selection.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start = if start.trim().is_empty() { 1 } else { parse_index(start)? };
+                let end = if end.trim().is_empty() { count } else { parse_index(end)? };
+
+                if start > end {
+                    return Err(AppError::logic(format!("Invalid playlist range: {}", part)));
+                }
+                indices.extend(start..=end);
+            }
+            None => indices.push(parse_index(part)?),
+        }
+    }
+
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(json: &str) -> FlatPlaylist {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn numbers_playlist_entries() {
+        let info = playlist_info(flat(
+            r#"{"_type":"playlist","id":"PL1","title":"Mix","channel":"Someone","entries":[
+                {"_type":"url","id":"a","title":"First","url":"https://www.youtube.com/watch?v=a","duration":61.0},
+                {"_type":"url","id":"b","title":"Second","url":"https://www.youtube.com/watch?v=b","duration":null}
+            ]}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(info.uploader.as_deref(), Some("Someone"));
+        assert_eq!(
+            info.entries.iter().map(|entry| (entry.index, entry.id.as_deref())).collect::<Vec<_>>(),
+            [(1, Some("a")), (2, Some("b"))]
+        );
+    }
+
+    #[test]
+    fn rejects_channel_tabs_and_single_videos() {
+        let channel = flat(
+            r#"{"_type":"playlist","id":"UC1","title":"Someone","entries":[
+                {"_type":"playlist","id":"UC1","title":"Someone - Videos","url":"https://www.youtube.com/@someone/videos"},
+                {"_type":"playlist","id":"UC1","title":"Someone - Shorts","url":"https://www.youtube.com/@someone/shorts"}
+            ]}"#,
+        );
+        assert!(matches!(
+            playlist_info(channel),
+            Err(AppError::Logic(ref message)) if message.contains("https://www.youtube.com/@someone/videos")
+        ));
+
+        let video = flat(r#"{"_type":"video","id":"a","title":"Single"}"#);
+        assert!(matches!(playlist_info(video), Err(AppError::Logic(_))));
+    }
+
+    #[test]
+    fn parses_indices_and_ranges() {
+        assert_eq!(parse_index_ranges("1-3,5", 10).unwrap(), vec![1, 2, 3, 5]);
+        assert_eq!(parse_index_ranges(" 8 , 2-3 ,", 10).unwrap(), vec![2, 3, 8]);
+        assert_eq!(parse_index_ranges("4,2-5,4", 10).unwrap(), vec![2, 3, 4, 5]);
+        assert_eq!(parse_index_ranges("7", 7).unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn open_ranges_run_to_the_playlist_bounds() {
+        assert_eq!(parse_index_ranges("8-", 10).unwrap(), vec![8, 9, 10]);
+        assert_eq!(parse_index_ranges("-2", 10).unwrap(), vec![1, 2]);
+        assert_eq!(parse_index_ranges("", 3).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_index_ranges("  ", 2).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn rejects_reversed_zero_and_out_of_range_indices() {
+        for selection in ["5-2", "0", "0-3", "11", "3-11", "12-", "abc", "1-x", "-1-3"] {
+            assert!(
+                matches!(parse_index_ranges(selection, 10), Err(AppError::Logic(_))),
+                "accepted {:?}",
+                selection
+            );
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Metadata probe command.
+//!
+//! Runs yt-dlp in metadata-only mode so the UI can preview an item and
+//! offer only the qualities that actually exist before downloading.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::commands::downloader::run_yt_dlp_json;
+use crate::commands::settings::SettingsState;
+use crate::error::AppError;
+use crate::utils::template::{build_output_template, resolve_template, validate_template};
+use crate::utils::validation::{canonicalize_output_dir, normalize_url};
+
+/// A single format offered by the source.
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatInfo {
+    pub format_id: String,
+    pub ext: Option<String>,
+    pub note: Option<String>,
+    pub acodec: Option<String>,
+    pub vcodec: Option<String>,
+    /// Audio bitrate in kbit/s.
+    pub abr: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fps: Option<f64>,
+    pub filesize: Option<u64>,
+}
+
+impl FormatInfo {
+    /// Whether the format carries an audio stream.
+    pub fn has_audio(&self) -> bool {
+        self.acodec.as_deref().is_some_and(|codec| codec != "none")
+    }
+}
+
+/// Response structure for a probed URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaInfo {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub uploader: Option<String>,
+    /// Duration in seconds.
+    pub duration: Option<f64>,
+    pub thumbnail: Option<String>,
+    pub formats: Vec<FormatInfo>,
+    /// Distinct audio bitrates (kbit/s) across all audio formats, ascending.
+    pub audio_bitrates: Vec<u32>,
+    pub is_playlist: bool,
+    /// Number of entries when the URL is a playlist or channel.
+    pub entry_count: Option<usize>,
+}
+
+/// yt-dlp `-J` output structures.
+#[derive(Deserialize)]
+struct YtDlpInfo {
+    #[serde(rename = "_type")]
+    kind: Option<String>,
+    id: Option<String>,
+    title: Option<String>,
+    uploader: Option<String>,
+    channel: Option<String>,
+    duration: Option<f64>,
+    thumbnail: Option<String>,
+    #[serde(default)]
+    formats: Vec<YtDlpFormat>,
+    playlist_count: Option<usize>,
+    entries: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct YtDlpFormat {
+    format_id: String,
+    ext: Option<String>,
+    format_note: Option<String>,
+    acodec: Option<String>,
+    vcodec: Option<String>,
+    abr: Option<f64>,
+    width: Option<u32>,
+    height: Option<u32>,
+    fps: Option<f64>,
+    filesize: Option<f64>,
+    filesize_approx: Option<f64>,
+}
+
+impl From<YtDlpFormat> for FormatInfo {
+    fn from(format: YtDlpFormat) -> Self {
+        Self {
+            format_id: format.format_id,
+            ext: format.ext,
+            note: format.format_note,
+            acodec: format.acodec,
+            vcodec: format.vcodec,
+            abr: format.abr,
+            width: format.width,
+            height: format.height,
+            fps: format.fps,
+            filesize: format.filesize.or(format.filesize_approx).map(|size| size as u64),
+        }
+    }
+}
+
+/// Probe a URL for metadata without downloading it.
+///
+/// Playlists are listed flat, so probing a channel does not fetch every video.
+#[tauri::command]
+pub async fn probe_url(
+    app: AppHandle,
+    settings: State<'_, SettingsState>,
+    url: String,
+) -> Result<MediaInfo, AppError> {
+    let url = normalize_url(&url, &settings.allowed_sites())?;
+
+    let info: YtDlpInfo =
+        run_yt_dlp_json(&app, &["-J", "--skip-download", "--flat-playlist", "--no-playlist"], &url).await?;
+
+    let is_playlist = info.kind.as_deref() == Some("playlist");
+    let entry_count = info
+        .playlist_count
+        .or_else(|| info.entries.as_ref().map(Vec::len));
+
+    let formats: Vec<FormatInfo> = info.formats.into_iter().map(FormatInfo::from).collect();
+
+    let mut audio_bitrates: Vec<u32> = formats
+        .iter()
+        .filter(|format| format.has_audio())
+        .filter_map(|format| format.abr)
+        .map(|abr| abr.round() as u32)
+        .filter(|abr| *abr > 0)
+        .collect();
+    audio_bitrates.sort_unstable();
+    audio_bitrates.dedup();
+
+    Ok(MediaInfo {
+        id: info.id,
+        title: info.title,
+        uploader: info.uploader.or(info.channel),
+        duration: info.duration,
+        thumbnail: info.thumbnail,
+        formats,
+        audio_bitrates,
+        is_playlist,
+        entry_count: if is_playlist { entry_count } else { None },
+    })
+}
+
+/// Preview the output path a filename template resolves to for a URL.
+///
+/// The template, URL and output folder are validated first, so invalid
+/// input fails without running yt-dlp. The extension shown is that of the
+/// source media.
+#[tauri::command]
+pub async fn preview_output_template(
+    app: AppHandle,
+    settings: State<'_, SettingsState>,
+    url: String,
+    output_path: String,
+    template: String,
+) -> Result<String, AppError> {
+    validate_template(&template)?;
+    let url = normalize_url(&url, &settings.allowed_sites())?;
+    let output_dir = canonicalize_output_dir(&output_path)?;
+
+    let info: serde_json::Value =
+        run_yt_dlp_json(&app, &["-J", "--skip-download", "--no-playlist"], &url).await?;
+
+    let file_name = resolve_template(&build_output_template(&template, false), &info)?;
+
+    Ok(output_dir.join(file_name).to_string_lossy().to_string())
+}
@@ -0,0 +1,455 @@
+//! Download queue commands.
+//!
+//! Provides a backend download queue managed in Tauri state. Jobs are run
+//! by a configurable number of concurrent yt-dlp workers and the queue is
+//! persisted to disk so pending jobs survive an app restart.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::control::ProcessRegistry;
+use crate::commands::downloader::{run_download, DownloadOutcome, DownloadRequest, SkipReason};
+use crate::commands::settings::SettingsState;
+use crate::config::{DEFAULT_QUEUE_WORKERS, EVENT_QUEUE_UPDATED, MAX_QUEUE_WORKERS, QUEUE_FILENAME};
+use crate::error::AppError;
+use crate::utils::paths::{app_data_path, set_aside};
+
+/// Lifecycle state of a queued job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Paused,
+    Completed,
+    Skipped,
+    Failed,
+    Cancelled,
+}
+
+/// A single job in the download queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedJob {
+    pub id: String,
+    pub request: DownloadRequest,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub skip_reason: Option<SkipReason>,
+}
+
+/// The download queue as persisted to disk and sent to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadQueue {
+    pub jobs: Vec<QueuedJob>,
+    pub max_workers: usize,
+}
+
+impl Default for DownloadQueue {
+    fn default() -> Self {
+        Self {
+            jobs: Vec::new(),
+            max_workers: DEFAULT_QUEUE_WORKERS,
+        }
+    }
+}
+
+/// The download queue as returned by `get_queue`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueResponse {
+    #[serde(flatten)]
+    pub queue: DownloadQueue,
+    /// Problems found while loading the saved queue.
+    pub warnings: Vec<String>,
+}
+
+/// Tauri-managed state wrapping the download queue.
+#[derive(Default)]
+pub struct QueueState {
+    queue: Mutex<DownloadQueue>,
+    warnings: Vec<String>,
+}
+
+impl QueueState {
+    pub fn new(queue: DownloadQueue, warnings: Vec<String>) -> Self {
+        Self {
+            queue: Mutex::new(queue),
+            warnings,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DownloadQueue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Check whether a job with the given ID is in the queue.
+    pub fn contains(&self, job_id: &str) -> bool {
+        self.lock().jobs.iter().any(|job| job.id == job_id)
+    }
+}
+
+/// Resolve the queue persistence file in the app data directory.
+fn queue_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app_data_path(app, QUEUE_FILENAME)
+}
+
+/// Load the persisted queue from disk.
+///
+/// Finished jobs are dropped and jobs that were running when the app
+/// closed are reset to pending (yt-dlp resumes from the partial files).
+/// A missing file yields an empty queue. A corrupt or unreadable file is
+/// moved aside so it is not overwritten, and a warning is returned.
+pub fn load_queue(app: &AppHandle) -> (DownloadQueue, Vec<String>) {
+    match queue_file_path(app) {
+        Ok(path) => read_queue(&path),
+        Err(e) => (DownloadQueue::default(), vec![format!("Saved download queue not loaded: {}", e)]),
+    }
+}
+
+/// Read and restore the queue file at `path`; see [`load_queue`].
+fn read_queue(path: &Path) -> (DownloadQueue, Vec<String>) {
+    let parsed = match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<DownloadQueue>(&content).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(DownloadQueue::default()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    let mut warnings = Vec::new();
+    let mut queue = parsed.unwrap_or_else(|error| {
+        warnings.push(match set_aside(path) {
+            Ok(backup) => format!(
+                "Saved download queue could not be read ({}); it was kept as {}.",
+                error,
+                backup.display()
+            ),
+            Err(e) => format!("Saved download queue could not be read ({}) or moved aside: {}", error, e),
+        });
+        DownloadQueue::default()
+    });
+
+    queue.jobs.retain(|job| {
+        matches!(
+            job.status,
+            JobStatus::Pending | JobStatus::Running | JobStatus::Paused
+        )
+    });
+
+    for job in &mut queue.jobs {
+        if job.status == JobStatus::Running {
+            job.status = JobStatus::Pending;
+        }
+    }
+
+    queue.max_workers = queue.max_workers.clamp(1, MAX_QUEUE_WORKERS);
+    (queue, warnings)
+}
+
+/// Write the queue to disk and notify the frontend.
+fn persist_and_emit(app: &AppHandle, queue: &DownloadQueue) -> Result<(), AppError> {
+    let _ = app.emit(EVENT_QUEUE_UPDATED, queue);
+
+    let path = queue_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(queue)
+        .map_err(|e| AppError::logic(format!("Failed to serialize queue: {}", e)))?;
+    fs::write(&path, json)?;
+
+    Ok(())
+}
+
+/// Start pending jobs until the worker limit is reached.
+///
+/// Each started job runs on its own task and pumps the queue again
+/// when it finishes, so freed worker slots are refilled immediately.
+pub fn pump_queue(app: &AppHandle) {
+    let state = app.state::<QueueState>();
+    let registry = app.state::<ProcessRegistry>();
+
+    let started: Vec<QueuedJob> = {
+        let mut queue = state.lock();
+        let running = queue
+            .jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Running)
+            .count();
+        let free_slots = queue.max_workers.saturating_sub(running);
+
+        let mut started = Vec::new();
+        for job in queue
+            .jobs
+            .iter_mut()
+            .filter(|job| job.status == JobStatus::Pending)
+            .take(free_slots)
+        {
+            // Tracked before the lock is released, so a cancel or pause
+            // that comes before the task starts is not lost
+            registry.reserve(&job.id);
+            job.status = JobStatus::Running;
+            started.push(job.clone());
+        }
+
+        if !started.is_empty() {
+            let _ = persist_and_emit(app, &queue);
+        }
+        started
+    };
+
+    for job in started {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = run_download(&app, &job.id, &job.request).await;
+            finish_job(&app, &job.id, result);
+            pump_queue(&app);
+        });
+    }
+}
+
+/// Record the outcome of a finished job.
+fn finish_job(app: &AppHandle, job_id: &str, result: Result<DownloadOutcome, AppError>) {
+    update_job(app, job_id, |job| match result {
+        Ok(DownloadOutcome::Completed { file_path }) => {
+            job.status = JobStatus::Completed;
+            job.result = file_path;
+        }
+        Ok(DownloadOutcome::Skipped { reason, file_path }) => {
+            job.status = JobStatus::Skipped;
+            job.result = file_path;
+            job.skip_reason = Some(reason);
+        }
+        Ok(DownloadOutcome::Paused) => {
+            job.status = JobStatus::Paused;
+        }
+        Ok(DownloadOutcome::Cancelled) => {
+            job.status = JobStatus::Cancelled;
+        }
+        Err(e) => {
+            job.status = JobStatus::Failed;
+            job.error = Some(e.to_string());
+        }
+    });
+}
+
+/// Apply a change to a queued job, then persist and emit the queue.
+///
+/// Returns `false` if the job is not in the queue.
+pub fn update_job<F>(app: &AppHandle, job_id: &str, update: F) -> bool
+where
+    F: FnOnce(&mut QueuedJob),
+{
+    let state = app.state::<QueueState>();
+    let mut queue = state.lock();
+
+    let Some(job) = queue.jobs.iter_mut().find(|job| job.id == job_id) else {
+        return false;
+    };
+    update(job);
+
+    let _ = persist_and_emit(app, &queue);
+    true
+}
+
+/// Get a snapshot of the current download queue.
+#[tauri::command]
+pub async fn get_queue(state: State<'_, QueueState>) -> Result<QueueResponse, AppError> {
+    Ok(QueueResponse {
+        queue: state.lock().clone(),
+        warnings: state.warnings.clone(),
+    })
+}
+
+/// Add download requests to the end of the queue and start workers.
+///
+/// Returns the IDs assigned to the new jobs.
+pub fn enqueue(app: &AppHandle, requests: Vec<DownloadRequest>) -> Result<Vec<String>, AppError> {
+    if requests.is_empty() {
+        return Err(AppError::logic("No downloads provided."));
+    }
+
+    let allowed_sites = app.state::<SettingsState>().allowed_sites();
+    let requests = requests
+        .into_iter()
+        .map(|request| request.normalized(&allowed_sites))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ids = {
+        let state = app.state::<QueueState>();
+        let mut queue = state.lock();
+        let mut ids = Vec::with_capacity(requests.len());
+
+        for request in requests {
+            let id = uuid::Uuid::new_v4().to_string();
+            queue.jobs.push(QueuedJob {
+                id: id.clone(),
+                request,
+                status: JobStatus::Pending,
+                result: None,
+                error: None,
+                skip_reason: None,
+            });
+            ids.push(id);
+        }
+
+        persist_and_emit(app, &queue)?;
+        ids
+    };
+
+    pump_queue(app);
+    Ok(ids)
+}
+
+/// Add one or more downloads to the end of the queue.
+///
+/// Returns the IDs assigned to the new jobs.
+#[tauri::command]
+pub async fn enqueue_downloads(app: AppHandle, requests: Vec<DownloadRequest>) -> Result<Vec<String>, AppError> {
+    enqueue(&app, requests)
+}
+
+/// Remove a job from the queue.
+///
+/// Running jobs cannot be removed. A paused job is discarded together with
+/// its partial files, as if it were cancelled.
+#[tauri::command]
+pub async fn dequeue_download(
+    app: AppHandle,
+    state: State<'_, QueueState>,
+    registry: State<'_, ProcessRegistry>,
+    job_id: String,
+) -> Result<(), AppError> {
+    let mut queue = state.lock();
+
+    let index = queue
+        .jobs
+        .iter()
+        .position(|job| job.id == job_id)
+        .ok_or_else(|| AppError::logic(format!("Job not found: {}", job_id)))?;
+
+    if queue.jobs[index].status == JobStatus::Running {
+        return Err(AppError::logic(
+            "Cannot remove a job that is currently running. Cancel it first.",
+        ));
+    }
+
+    if queue.jobs[index].status == JobStatus::Paused {
+        registry.discard_paused(&job_id);
+    }
+
+    queue.jobs.remove(index);
+    persist_and_emit(&app, &queue)
+}
+
+/// Move a job to a new position in the queue.
+#[tauri::command]
+pub async fn reorder_queue(
+    app: AppHandle,
+    state: State<'_, QueueState>,
+    job_id: String,
+    new_index: usize,
+) -> Result<(), AppError> {
+    let mut queue = state.lock();
+
+    let index = queue
+        .jobs
+        .iter()
+        .position(|job| job.id == job_id)
+        .ok_or_else(|| AppError::logic(format!("Job not found: {}", job_id)))?;
+
+    let job = queue.jobs.remove(index);
+    let new_index = new_index.min(queue.jobs.len());
+    queue.jobs.insert(new_index, job);
+
+    persist_and_emit(&app, &queue)
+}
+
+/// Set the number of concurrent yt-dlp workers.
+#[tauri::command]
+pub async fn set_queue_workers(
+    app: AppHandle,
+    state: State<'_, QueueState>,
+    count: usize,
+) -> Result<(), AppError> {
+    if count == 0 || count > MAX_QUEUE_WORKERS {
+        return Err(AppError::logic(format!(
+            "Worker count must be between 1 and {}.",
+            MAX_QUEUE_WORKERS
+        )));
+    }
+
+    {
+        let mut queue = state.lock();
+        queue.max_workers = count;
+        persist_and_emit(&app, &queue)?;
+    }
+
+    pump_queue(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "status": status,
+            "request": {
+                "url": "https://www.youtube.com/watch?v=abc",
+                "output_path": "/tmp",
+                "mode": { "mode": "audio", "format": { "format": "best" } }
+            }
+        })
+    }
+
+    #[test]
+    fn restores_unfinished_jobs() {
+        let dir = std::env::temp_dir().join(format!("godspeed-queue-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(QUEUE_FILENAME);
+
+        let (queue, warnings) = read_queue(&path);
+        assert!(queue.jobs.is_empty() && warnings.is_empty());
+
+        let saved = serde_json::json!({
+            "jobs": [job("a", "running"), job("b", "completed"), job("c", "paused"), job("d", "failed")],
+            "max_workers": 99
+        });
+        fs::write(&path, saved.to_string()).unwrap();
+        let (queue, warnings) = read_queue(&path);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let jobs: Vec<_> = queue.jobs.iter().map(|job| (job.id.as_str(), job.status)).collect();
+        assert_eq!(jobs, [("a", JobStatus::Pending), ("c", JobStatus::Paused)]);
+        assert_eq!(queue.max_workers, MAX_QUEUE_WORKERS);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_queue_is_kept_as_a_backup() {
+        let dir = std::env::temp_dir().join(format!("godspeed-queue-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(QUEUE_FILENAME);
+        fs::write(&path, "{\"jobs\": [").unwrap();
+
+        let (queue, warnings) = read_queue(&path);
+        assert!(queue.jobs.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert!(!path.exists());
+        let backup = dir.join(format!("{}.bad", QUEUE_FILENAME));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{\"jobs\": [");
+        assert!(warnings[0].contains(&backup.display().to_string()), "{}", warnings[0]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,705 @@
+//! User settings commands.
+//!
+//! Settings are stored as versioned JSON in the app config directory and
+//! held in Tauri state. Files written by older app versions are migrated
+//! to the current schema when they are loaded. The proxy password is kept
+//! in an encrypted secret store instead of the settings file.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::{
+    AudioFormat, EngineChannel, DEFAULT_ENGINE_INDEX_URL, DEFAULT_ENGINE_UPDATE_URL, DEFAULT_FILENAME_TEMPLATE,
+    ENV_APP_UPDATE_FEED, ENV_APP_UPDATE_URL, ENV_ENGINE_INDEX_URL, ENV_ENGINE_PACKAGE_URL, GITHUB_API_URL,
+    NETWORK_SECRETS_FILENAME, SETTINGS_FILENAME, SETTINGS_VERSION,
+};
+use crate::error::AppError;
+use crate::utils::network::NetworkConfig;
+use crate::utils::paths::set_aside;
+use crate::utils::secrets::app_secret_store;
+use crate::utils::template::validate_template;
+use crate::utils::validation::{canonicalize_output_dir, validate_site};
+
+/// Name of the proxy password in the network secret store.
+const PROXY_PASSWORD_SECRET: &str = "proxy_password";
+
+/// Persisted user settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Schema version the settings were written with.
+    pub version: u32,
+    /// Download directory.
+    pub output_path: String,
+    /// Audio format and quality for audio downloads.
+    pub audio_format: AudioFormat,
+    /// Filename template relative to `output_path`, without extension.
+    pub filename_template: String,
+    /// Clear the URL input after a successful download.
+    pub auto_clear_url: bool,
+    /// Show the terminal toggle and yt-dlp output.
+    pub terminal_enabled: bool,
+    /// Where app and engine updates are fetched from.
+    pub update_sources: UpdateSources,
+    /// Release channel used when updating yt-dlp.
+    pub yt_dlp_channel: EngineChannel,
+    /// Offer pre-release (beta) versions of the app as updates.
+    pub beta_updates: bool,
+    /// Host names downloads are restricted to; empty allows any site.
+    pub allowed_sites: Vec<String>,
+    /// Proxy, bandwidth limit, source address and CA bundle.
+    pub network: NetworkConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            output_path: default_output_path(),
+            audio_format: AudioFormat::default(),
+            filename_template: DEFAULT_FILENAME_TEMPLATE.to_string(),
+            auto_clear_url: true,
+            terminal_enabled: false,
+            update_sources: UpdateSources::default(),
+            yt_dlp_channel: EngineChannel::default(),
+            beta_updates: false,
+            allowed_sites: Vec::new(),
+            network: NetworkConfig::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Validate the settings before they are saved.
+    pub fn validate(&self) -> Result<(), AppError> {
+        ensure_writable_dir(&canonicalize_output_dir(&self.output_path)?)?;
+        self.audio_format.validate()?;
+        validate_template(&self.filename_template)?;
+
+        for site in &self.allowed_sites {
+            validate_site(site)?;
+        }
+        self.network.validate()?;
+
+        self.update_sources.validate()
+    }
+}
+
+/// Format of the app update feed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppFeedFormat {
+    /// GitHub Releases API (`.../releases/latest`).
+    #[default]
+    GithubReleases,
+    /// Static JSON manifest listing releases and their assets.
+    Manifest,
+}
+
+/// Update endpoints, so releases can be mirrored (e.g. on a corporate network).
+///
+/// Each value can be overridden at runtime through an environment variable
+/// (see `ENV_*` in `config.rs`); overrides are never written to disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSources {
+    pub app_feed: AppFeedFormat,
+    /// App update feed: a GitHub `releases/latest` API URL or a static manifest.
+    pub app_url: String,
+    /// Engine package used by "Reinstall / Update Engine" (with `<url>.manifest.json`).
+    pub engine_package_url: String,
+    /// Engine index used by per-binary engine updates.
+    pub engine_index_url: String,
+}
+
+impl Default for UpdateSources {
+    fn default() -> Self {
+        Self {
+            app_feed: AppFeedFormat::default(),
+            app_url: GITHUB_API_URL.to_string(),
+            engine_package_url: DEFAULT_ENGINE_UPDATE_URL.to_string(),
+            engine_index_url: DEFAULT_ENGINE_INDEX_URL.to_string(),
+        }
+    }
+}
+
+impl UpdateSources {
+    fn validate(&self) -> Result<(), AppError> {
+        validate_update_url("App update", &self.app_url)?;
+        validate_update_url("Engine package", &self.engine_package_url)?;
+        validate_update_url("Engine index", &self.engine_index_url)
+    }
+}
+
+/// Update endpoints set through environment variables (see `ENV_*` in `config.rs`).
+///
+/// Read and validated once at startup; rejected values are kept as warnings
+/// for the settings view instead of being applied.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateSourceOverrides {
+    app_feed: Option<AppFeedFormat>,
+    app_url: Option<String>,
+    engine_package_url: Option<String>,
+    engine_index_url: Option<String>,
+    /// One message per rejected override.
+    warnings: Vec<String>,
+}
+
+impl UpdateSourceOverrides {
+    /// Read the overrides from the environment.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the overrides through `lookup`; blank values count as unset.
+    ///
+    /// URL overrides get the same https check as saved URLs.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let env = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+        let mut warnings = Vec::new();
+        let mut url_env = |name: &str, label: &str| {
+            let url = env(name)?;
+            match validate_update_url(label, &url) {
+                Ok(()) => Some(url),
+                Err(e) => {
+                    warnings.push(format!("Ignoring {}: {}", name, e));
+                    None
+                }
+            }
+        };
+
+        let app_url = url_env(ENV_APP_UPDATE_URL, "App update");
+        let engine_package_url = url_env(ENV_ENGINE_PACKAGE_URL, "Engine package");
+        let engine_index_url = url_env(ENV_ENGINE_INDEX_URL, "Engine index");
+
+        let app_feed = env(ENV_APP_UPDATE_FEED).and_then(|feed| {
+            match serde_json::from_value::<AppFeedFormat>(Value::String(feed.clone())) {
+                Ok(format) => Some(format),
+                Err(_) => {
+                    warnings.push(format!("Ignoring {}: unknown feed format \"{}\"", ENV_APP_UPDATE_FEED, feed));
+                    None
+                }
+            }
+        });
+
+        Self {
+            app_feed,
+            app_url,
+            engine_package_url,
+            engine_index_url,
+            warnings,
+        }
+    }
+
+    /// Apply the accepted overrides to the saved endpoints.
+    fn apply(&self, mut sources: UpdateSources) -> UpdateSources {
+        if let Some(feed) = self.app_feed {
+            sources.app_feed = feed;
+        }
+        if let Some(url) = &self.app_url {
+            sources.app_url = url.clone();
+        }
+        if let Some(url) = &self.engine_package_url {
+            sources.engine_package_url = url.clone();
+        }
+        if let Some(url) = &self.engine_index_url {
+            sources.engine_index_url = url.clone();
+        }
+
+        sources
+    }
+}
+
+/// Require https, except for a local stand-in server on the loopback interface.
+pub(crate) fn validate_update_url(label: &str, url: &str) -> Result<(), AppError> {
+    let is_loopback = ["http://localhost", "http://127.0.0.1", "http://[::1]"]
+        .iter()
+        .any(|prefix| {
+            url.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/']))
+        });
+
+    if !url.starts_with("https://") && !is_loopback {
+        return Err(AppError::logic(format!("{} URL must use https://", label)));
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn default_output_path() -> String {
+    crate::config::DEFAULT_OUTPUT_PATH.to_string()
+}
+
+/// Resolved to the user's Downloads folder when the settings are loaded.
+#[cfg(not(target_os = "windows"))]
+fn default_output_path() -> String {
+    String::new()
+}
+
+/// Schema migrations, applied in order.
+///
+/// Entry `i` upgrades a version `i + 1` document to version `i + 2`, so
+/// `SETTINGS_VERSION` must always equal `MIGRATIONS.len() + 1`.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[migrate_v1_update_sources];
+
+/// v1 -> v2: `engine_update_url` moved into `update_sources`.
+fn migrate_v1_update_sources(document: &mut Map<String, Value>) {
+    let mut sources = Map::new();
+    if let Some(url) = document.remove("engine_update_url") {
+        sources.insert("engine_package_url".to_string(), url);
+    }
+
+    document.insert("update_sources".to_string(), Value::Object(sources));
+}
+
+/// Settings as returned to the frontend, with problems found at startup.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsResponse {
+    #[serde(flatten)]
+    pub settings: Settings,
+    /// Settings file problems and rejected environment overrides.
+    pub warnings: Vec<String>,
+}
+
+/// Tauri-managed state wrapping the current settings.
+pub struct SettingsState {
+    settings: Mutex<Settings>,
+    /// Problems found while loading the settings file.
+    load_warnings: Vec<String>,
+    overrides: UpdateSourceOverrides,
+}
+
+impl SettingsState {
+    pub fn new(settings: Settings, load_warnings: Vec<String>, overrides: UpdateSourceOverrides) -> Self {
+        Self {
+            settings: Mutex::new(settings),
+            load_warnings,
+            overrides,
+        }
+    }
+
+    /// A snapshot of the current settings.
+    pub fn current(&self) -> Settings {
+        self.lock().clone()
+    }
+
+    /// The update endpoints in effect, including environment overrides.
+    pub fn update_sources(&self) -> UpdateSources {
+        self.overrides.apply(self.lock().update_sources.clone())
+    }
+
+    /// The network settings applied to yt-dlp, aria2c and HTTP clients.
+    pub fn network(&self) -> NetworkConfig {
+        self.lock().network.clone()
+    }
+
+    /// The filename template for downloads that do not set their own.
+    pub fn filename_template(&self) -> String {
+        self.lock().filename_template.clone()
+    }
+
+    /// The site allowlist applied to download URLs.
+    pub fn allowed_sites(&self) -> Vec<String> {
+        self.lock().allowed_sites.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Settings> {
+        self.settings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Pair `settings` with the startup warnings for the frontend.
+    fn response(&self, settings: Settings) -> SettingsResponse {
+        SettingsResponse {
+            settings,
+            warnings: [&self.load_warnings[..], &self.overrides.warnings[..]].concat(),
+        }
+    }
+}
+
+/// Resolve the settings file in the app config directory.
+fn settings_file_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| AppError::tauri(format!("Failed to resolve app config directory: {}", e)))?;
+
+    Ok(config_dir.join(SETTINGS_FILENAME))
+}
+
+/// Load the settings from disk, migrating older schema versions.
+///
+/// Missing fields take their default values; see [`read_settings`] for
+/// unreadable files and fields.
+pub fn load_settings(app: &AppHandle) -> (Settings, Vec<String>) {
+    let (mut settings, warnings) = match settings_file_path(app) {
+        Ok(path) => read_settings(&path),
+        Err(e) => (Settings::default(), vec![format!("Saved settings not loaded: {}", e)]),
+    };
+
+    if settings.output_path.is_empty() {
+        if let Ok(dir) = app.path().download_dir() {
+            settings.output_path = dir.to_string_lossy().to_string();
+        }
+    }
+
+    // A password typed into the proxy URL by an older version moves into the secret store
+    settings.network.proxy_password = match settings.network.take_proxy_password() {
+        Some(password) => {
+            if store_proxy_password(app, Some(&password)).is_ok() {
+                let _ = persist(app, &settings);
+            }
+            Some(password)
+        }
+        None => load_proxy_password(app),
+    };
+
+    (settings, warnings)
+}
+
+/// Read and migrate the settings file at `path`.
+///
+/// A missing file yields the default settings. Fields that cannot be read
+/// fall back to their defaults one by one. If the file or any field cannot
+/// be read, the file is moved aside so the next save does not overwrite
+/// it, and a warning is returned.
+fn read_settings(path: &Path) -> (Settings, Vec<String>) {
+    let document = match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<Map<String, Value>>(&content).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == ErrorKind::NotFound => return (Settings::default(), Vec::new()),
+        Err(e) => Err(e.to_string()),
+    };
+
+    let (settings, problem) = match document {
+        Ok(document) => {
+            let (settings, rejected) = overlay_fields(migrate(document));
+            let problem = (!rejected.is_empty()).then(|| format!("invalid {}", rejected.join(", ")));
+            (settings, problem)
+        }
+        Err(error) => (Settings::default(), Some(error)),
+    };
+
+    let warnings = match problem {
+        None => Vec::new(),
+        Some(problem) => vec![match set_aside(path) {
+            Ok(backup) => format!(
+                "Some settings could not be read ({}) and were reset to defaults; the old file was kept as {}.",
+                problem,
+                backup.display()
+            ),
+            Err(e) => format!(
+                "Some settings could not be read ({}) and were reset to defaults; the old file could not be moved aside: {}",
+                problem, e
+            ),
+        }],
+    };
+
+    (settings, warnings)
+}
+
+/// Apply each field of `document` to the default settings.
+///
+/// Returns the settings and the names of the fields that were rejected.
+fn overlay_fields(document: Map<String, Value>) -> (Settings, Vec<String>) {
+    let mut merged = match serde_json::to_value(Settings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => Map::new(),
+    };
+    let mut rejected = Vec::new();
+
+    for (key, value) in document {
+        let mut candidate = merged.clone();
+        candidate.insert(key.clone(), value);
+        if serde_json::from_value::<Settings>(Value::Object(candidate.clone())).is_ok() {
+            merged = candidate;
+        } else {
+            rejected.push(key);
+        }
+    }
+
+    let settings = serde_json::from_value(Value::Object(merged)).unwrap_or_default();
+    (settings, rejected)
+}
+
+/// Read the proxy password from the secret store.
+fn load_proxy_password(app: &AppHandle) -> Option<String> {
+    app_secret_store(app, NETWORK_SECRETS_FILENAME)
+        .ok()?
+        .load()
+        .ok()?
+        .remove(PROXY_PASSWORD_SECRET)
+}
+
+/// Save the proxy password to the secret store, or remove it if `None`.
+fn store_proxy_password(app: &AppHandle, password: Option<&str>) -> Result<(), AppError> {
+    let store = app_secret_store(app, NETWORK_SECRETS_FILENAME)?;
+    let mut secrets = store.load_for_update()?;
+
+    let changed = match password {
+        Some(password) => {
+            secrets.insert(PROXY_PASSWORD_SECRET.to_string(), password.to_string()).as_deref() != Some(password)
+        }
+        None => secrets.remove(PROXY_PASSWORD_SECRET).is_some(),
+    };
+
+    if changed {
+        store.save(&secrets)?;
+    }
+
+    Ok(())
+}
+
+/// Upgrade a settings document to `SETTINGS_VERSION`.
+///
+/// Documents without a version are treated as version 1. Documents from a
+/// newer app version are left untouched and read as-is; fields this version
+/// does not know about are dropped on the next save.
+fn migrate(mut document: Map<String, Value>) -> Map<String, Value> {
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+        .max(1);
+
+    if version >= u64::from(SETTINGS_VERSION) {
+        return document;
+    }
+
+    for migration in MIGRATIONS.iter().skip(version as usize - 1) {
+        migration(&mut document);
+    }
+
+    document.insert("version".to_string(), SETTINGS_VERSION.into());
+    document
+}
+
+/// Check that files can be created in a canonical output directory.
+fn ensure_writable_dir(path: &Path) -> Result<(), AppError> {
+    let probe = path.join(format!(".godspeed-write-test-{}", uuid::Uuid::new_v4()));
+    fs::File::create(&probe).map_err(|e| {
+        AppError::InvalidOutputPath(format!(
+            "Output folder is not writable: {} ({})",
+            path.display(),
+            e
+        ))
+    })?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Write the settings to disk.
+fn persist(app: &AppHandle, settings: &Settings) -> Result<(), AppError> {
+    let path = settings_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| AppError::logic(format!("Failed to serialize settings: {}", e)))?;
+    fs::write(&path, json)?;
+
+    Ok(())
+}
+
+/// Get the current settings.
+#[tauri::command]
+pub async fn get_settings(state: State<'_, SettingsState>) -> Result<SettingsResponse, AppError> {
+    Ok(state.response(state.current()))
+}
+
+/// Validate, save and apply new settings.
+///
+/// Returns the settings as stored.
+#[tauri::command]
+pub async fn update_settings(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    settings: Settings,
+) -> Result<SettingsResponse, AppError> {
+    let mut settings = settings;
+
+    // A proxy URL with a user but no password keeps the saved password
+    let proxy_password = match settings.network.take_proxy_password() {
+        Some(password) => Some(password),
+        None if settings.network.has_proxy_user() => state.network().proxy_password,
+        None => None,
+    };
+    settings.network.proxy_password = proxy_password;
+
+    settings.validate()?;
+
+    let settings = Settings {
+        version: SETTINGS_VERSION,
+        ..settings
+    };
+    store_proxy_password(&app, settings.network.proxy_password.as_deref())?;
+    persist(&app, &settings)?;
+
+    *state.lock() = settings.clone();
+    Ok(state.response(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn migrations_cover_every_version() {
+        assert_eq!(SETTINGS_VERSION as usize, MIGRATIONS.len() + 1);
+    }
+
+    #[test]
+    fn migrates_unversioned_documents_to_current() {
+        let migrated = migrate(document(serde_json::json!({
+            "output_path": "/music",
+            "engine_update_url": "https://mirror.example/engine.zip",
+        })));
+
+        assert_eq!(migrated["version"], SETTINGS_VERSION);
+        assert_eq!(migrated["output_path"], "/music");
+        assert!(!migrated.contains_key("engine_update_url"));
+        assert_eq!(
+            migrated["update_sources"]["engine_package_url"],
+            "https://mirror.example/engine.zip"
+        );
+
+        let settings: Settings = serde_json::from_value(Value::Object(migrated)).unwrap();
+        assert_eq!(settings.update_sources.engine_package_url, "https://mirror.example/engine.zip");
+        assert_eq!(settings.update_sources.engine_index_url, DEFAULT_ENGINE_INDEX_URL);
+    }
+
+    #[test]
+    fn v1_without_engine_url_gets_default_sources() {
+        let migrated = migrate(document(serde_json::json!({ "version": 1 })));
+        let settings: Settings = serde_json::from_value(Value::Object(migrated)).unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.update_sources.engine_package_url, DEFAULT_ENGINE_UPDATE_URL);
+    }
+
+    #[test]
+    fn current_documents_are_unchanged() {
+        let current = document(serde_json::json!({
+            "version": SETTINGS_VERSION,
+            "update_sources": { "engine_package_url": "https://mirror.example/engine.zip" },
+            "engine_update_url": "https://stale.example/engine.zip",
+        }));
+
+        assert_eq!(migrate(current.clone()), current);
+    }
+
+    #[test]
+    fn future_documents_are_left_untouched() {
+        let future = document(serde_json::json!({
+            "version": SETTINGS_VERSION + 1,
+            "output_path": "/music",
+            "new_option": true,
+        }));
+
+        assert_eq!(migrate(future.clone()), future);
+    }
+
+    #[test]
+    fn invalid_fields_fall_back_one_by_one() {
+        let (settings, rejected) = overlay_fields(document(serde_json::json!({
+            "output_path": "/music",
+            "audio_format": { "format": "vinyl" },
+            "auto_clear_url": "yes",
+            "beta_updates": true,
+        })));
+
+        assert_eq!(rejected, ["audio_format", "auto_clear_url"]);
+        assert_eq!(settings.output_path, "/music");
+        assert!(settings.beta_updates);
+        assert!(settings.auto_clear_url);
+        assert!(matches!(settings.audio_format, AudioFormat::Mp3 { .. }));
+    }
+
+    #[test]
+    fn unreadable_settings_are_kept_as_a_backup() {
+        let dir = std::env::temp_dir().join(format!("godspeed-settings-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILENAME);
+        let backup = dir.join(format!("{}.bad", SETTINGS_FILENAME));
+
+        let (_, warnings) = read_settings(&path);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let valid = serde_json::json!({ "version": SETTINGS_VERSION, "beta_updates": true }).to_string();
+        fs::write(&path, &valid).unwrap();
+        let (settings, warnings) = read_settings(&path);
+        assert!(settings.beta_updates);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert!(path.exists());
+
+        let partial = serde_json::json!({ "beta_updates": true, "allowed_sites": "youtube.com" }).to_string();
+        fs::write(&path, &partial).unwrap();
+        let (settings, warnings) = read_settings(&path);
+        assert!(settings.beta_updates);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("allowed_sites"), "{}", warnings[0]);
+        assert_eq!(fs::read_to_string(&backup).unwrap(), partial);
+
+        fs::write(&path, "{\"version\": 2,").unwrap();
+        let (settings, warnings) = read_settings(&path);
+        assert!(!settings.beta_updates);
+        assert_eq!(warnings.len(), 1);
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(dir.join(format!("{}.bad.1", SETTINGS_FILENAME))).unwrap(), "{\"version\": 2,");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn overrides(vars: &[(&str, &str)]) -> UpdateSourceOverrides {
+        UpdateSourceOverrides::from_lookup(|name| {
+            vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn applies_valid_overrides() {
+        let overrides = overrides(&[
+            (ENV_APP_UPDATE_FEED, "manifest"),
+            (ENV_APP_UPDATE_URL, "https://mirror.example/app.json"),
+            (ENV_ENGINE_INDEX_URL, "http://localhost:8080/index.json"),
+        ]);
+        let sources = overrides.apply(UpdateSources::default());
+
+        assert!(overrides.warnings.is_empty());
+        assert_eq!(sources.app_feed, AppFeedFormat::Manifest);
+        assert_eq!(sources.app_url, "https://mirror.example/app.json");
+        assert_eq!(sources.engine_package_url, DEFAULT_ENGINE_UPDATE_URL);
+        assert_eq!(sources.engine_index_url, "http://localhost:8080/index.json");
+    }
+
+    #[test]
+    fn rejected_overrides_become_warnings() {
+        let overrides = overrides(&[
+            (ENV_APP_UPDATE_FEED, "rss"),
+            (ENV_ENGINE_PACKAGE_URL, "http://mirror.example/engine.zip"),
+            (ENV_ENGINE_INDEX_URL, "   "),
+        ]);
+        let sources = overrides.apply(UpdateSources::default());
+
+        assert_eq!(overrides.warnings.len(), 2, "{:?}", overrides.warnings);
+        assert!(overrides.warnings.iter().any(|w| w.contains(ENV_APP_UPDATE_FEED)));
+        assert!(overrides.warnings.iter().any(|w| w.contains(ENV_ENGINE_PACKAGE_URL)));
+        assert_eq!(sources.app_feed, AppFeedFormat::GithubReleases);
+        assert_eq!(sources.engine_package_url, DEFAULT_ENGINE_UPDATE_URL);
+        assert_eq!(sources.engine_index_url, DEFAULT_ENGINE_INDEX_URL);
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! Centralizes all hardcoded values for easy maintenance and testing.
 
-/// GitHub API URL for checking releases.
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::utils::shlex::shlex_quote;
+
+/// GitHub API URL for checking releases (the default app update feed).
 pub const GITHUB_API_URL: &str =
     "https://api.github.com/repos/ThanathonTH/godspeed-downloader/releases/latest";
 
@@ -12,19 +17,121 @@ pub const USER_AGENT: &str = "godspeed-app";
 /// Download timeout in seconds (10 minutes for slow connections).
 pub const DOWNLOAD_TIMEOUT_SECS: u64 = 600;
 
-/// MSI installer filename for app updates.
-pub const UPDATE_MSI_FILENAME: &str = "Godspeed_Update.msi";
+/// Largest engine package or binary accepted by the update downloader.
+pub const MAX_ENGINE_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Largest app installer accepted by the update downloader.
+pub const MAX_APP_UPDATE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Largest signed engine manifest, index or signature read into memory.
+pub const MAX_SIGNED_DOCUMENT_BYTES: u64 = 1024 * 1024;
 
 // =============================================================================
 // Event Names
 // =============================================================================
 
-/// Event emitted when download progress updates.
+/// Event emitted when download progress updates (structured `ProgressEvent`).
 pub const EVENT_DOWNLOAD_PROGRESS: &str = "download-progress";
 
+/// Event emitted for each raw yt-dlp output line (terminal display).
+pub const EVENT_DOWNLOAD_LOG: &str = "download-log";
+
 /// Event emitted when download completes successfully.
 pub const EVENT_DOWNLOAD_COMPLETE: &str = "download-complete";
 
+/// Event emitted whenever the download queue changes.
+pub const EVENT_QUEUE_UPDATED: &str = "queue-updated";
+
+/// Event emitted with byte-level progress of engine and app update downloads.
+pub const EVENT_HTTP_DOWNLOAD_PROGRESS: &str = "http-download-progress";
+
+// =============================================================================
+// Download Queue
+// =============================================================================
+
+/// Queue persistence filename (stored in the app data directory).
+pub const QUEUE_FILENAME: &str = "queue.json";
+
+/// Default number of concurrent yt-dlp workers.
+pub const DEFAULT_QUEUE_WORKERS: usize = 2;
+
+/// Upper bound for concurrent yt-dlp workers.
+pub const MAX_QUEUE_WORKERS: usize = 8;
+
+// =============================================================================
+// Download History & Archive
+// =============================================================================
+
+/// Append-only history log (JSON Lines) inside the app data directory.
+pub const HISTORY_FILENAME: &str = "history.jsonl";
+
+/// yt-dlp `--download-archive` file inside the app data directory.
+pub const DOWNLOAD_ARCHIVE_FILENAME: &str = "download-archive.txt";
+
+/// yt-dlp exit code when `--break-on-existing` stops at an archived item.
+pub const YT_DLP_EXIT_ALREADY_ARCHIVED: i32 = 101;
+
+// =============================================================================
+// User Settings
+// =============================================================================
+
+/// Settings file name inside the app config directory.
+pub const SETTINGS_FILENAME: &str = "settings.json";
+
+/// Current settings schema version. Bump it together with a new migration.
+pub const SETTINGS_VERSION: u32 = 2;
+
+/// Default download directory on Windows.
+#[cfg(target_os = "windows")]
+pub const DEFAULT_OUTPUT_PATH: &str = "C:\\Downloads";
+
+/// Default engine package URL used by "Reinstall / Update Engine".
+///
+/// The release must also carry `<url>.manifest.json` and its `.minisig`,
+/// signed with the key embedded as `ENGINE_SIGNING_PUBLIC_KEY`.
+pub const DEFAULT_ENGINE_UPDATE_URL: &str =
+    "https://github.com/ThanathonTH/godspeed-downloader/releases/download/v2.1.0/engine_v12.zip";
+
+// =============================================================================
+// Update Source Overrides
+// =============================================================================
+
+/// Environment variable overriding the app update feed URL.
+pub const ENV_APP_UPDATE_URL: &str = "GODSPEED_APP_UPDATE_URL";
+
+/// Environment variable overriding the app update feed format (`github_releases` or `manifest`).
+pub const ENV_APP_UPDATE_FEED: &str = "GODSPEED_APP_UPDATE_FEED";
+
+/// Environment variable overriding the engine package URL.
+pub const ENV_ENGINE_PACKAGE_URL: &str = "GODSPEED_ENGINE_PACKAGE_URL";
+
+/// Environment variable overriding the engine index URL.
+pub const ENV_ENGINE_INDEX_URL: &str = "GODSPEED_ENGINE_INDEX_URL";
+
+// =============================================================================
+// Authentication Profiles
+// =============================================================================
+
+/// Auth profiles (without secrets) inside the app data directory.
+pub const AUTH_PROFILES_FILENAME: &str = "auth-profiles.json";
+
+/// Encrypted passwords and cookies inside the app data directory.
+pub const AUTH_SECRETS_FILENAME: &str = "auth-secrets.bin";
+
+/// Encryption key for `AUTH_SECRETS_FILENAME` inside the app local data directory.
+pub const AUTH_KEY_FILENAME: &str = "auth.key";
+
+/// Encrypted proxy password inside the app data directory (shares `AUTH_KEY_FILENAME`).
+pub const NETWORK_SECRETS_FILENAME: &str = "network-secrets.bin";
+
+/// Largest cookies file accepted for import (10 MiB).
+pub const MAX_COOKIES_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Browsers yt-dlp can read cookies from with `--cookies-from-browser`.
+pub const YT_DLP_COOKIE_BROWSERS: &[&str] = &[
+    "brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale",
+];
+
 // =============================================================================
 // Engine Binaries (Platform-Specific)
 // =============================================================================
@@ -44,17 +151,576 @@ pub const ENGINE_BINARIES: &[&str] = &["yt-dlp", "aria2c", "ffmpeg"];
 /// yt-dlp sidecar name (without extension, Tauri handles platform suffix).
 pub const YT_DLP_SIDECAR: &str = "yt-dlp";
 
+/// FFmpeg sidecar name (without extension), passed to yt-dlp for merging and conversion.
+pub const FFMPEG_SIDECAR: &str = "ffmpeg";
+
+/// Flag that makes each engine print its version, keyed by sidecar name.
+pub const ENGINE_VERSION_FLAGS: &[(&str, &str)] = &[
+    ("yt-dlp", "--version"),
+    ("aria2c", "--version"),
+    ("ffmpeg", "-version"),
+];
+
+// =============================================================================
+// Engine Update Integrity
+// =============================================================================
+
+/// Suffix appended to the file name in the engine package URL to locate its checksum manifest.
+///
+/// `engine_v12.zip` is published with `engine_v12.zip.manifest.json`, listing
+/// the SHA-256 of the archive and of every binary inside it.
+pub const ENGINE_MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// Suffix appended to the file name in a manifest or index URL to locate its minisign signature.
+pub const ENGINE_SIGNATURE_SUFFIX: &str = ".minisig";
+
+/// Minisign public key (base64) the engine manifests must be signed with.
+///
+/// Release builds embed the maintainer's release key from the
+/// `GODSPEED_ENGINE_SIGNING_PUBLIC_KEY` build variable. Every manifest needs
+/// a valid `.minisig` from this key; there is no unsigned fallback, so a
+/// build without the key cannot install engine updates at all.
+pub const ENGINE_SIGNING_PUBLIC_KEY: Option<&str> = option_env!("GODSPEED_ENGINE_SIGNING_PUBLIC_KEY");
+
+/// Environment variable replacing `ENGINE_SIGNING_PUBLIC_KEY` in debug builds,
+/// so a local mirror can be signed with a development key.
+#[cfg(debug_assertions)]
+pub const ENV_ENGINE_SIGNING_KEY: &str = "GODSPEED_ENGINE_SIGNING_KEY";
+
+// =============================================================================
+// Engine Update Index
+// =============================================================================
+
+/// Default index of the latest individual engine binaries (per-binary version, URL, SHA-256 and target).
+pub const DEFAULT_ENGINE_INDEX_URL: &str =
+    "https://github.com/ThanathonTH/godspeed-downloader/releases/latest/download/engine-index.json";
+
+/// Target triple this app was built for; index entries for other targets are ignored.
+pub const ENGINE_TARGET_TRIPLE: &str = env!("GODSPEED_TARGET_TRIPLE");
+
+/// Release channel of an engine binary.
+///
+/// Only yt-dlp publishes nightly builds; every other engine follows `Stable`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineChannel {
+    #[default]
+    Stable,
+    Nightly,
+}
+
+// =============================================================================
+// Engine Install
+// =============================================================================
+
+/// Directory (inside the binaries directory) where new binaries are staged before the swap.
+pub const ENGINE_STAGING_DIRNAME: &str = ".engine-staging";
+
+/// Directory (inside the binaries directory) holding the previous engine set for rollback.
+pub const ENGINE_BACKUP_DIRNAME: &str = ".engine-backup";
+
+/// Backup directory being built during a swap; replaces `ENGINE_BACKUP_DIRNAME` on success.
+pub const ENGINE_PENDING_BACKUP_DIRNAME: &str = ".engine-backup.pending";
+
+// =============================================================================
+// yt-dlp Machine-Readable Output
+// =============================================================================
+
+/// `--progress-template` for downloads: one JSON object per progress update.
+pub const YT_DLP_DOWNLOAD_TEMPLATE: &str = r#"download:{"type":"download","progress":%(progress)j}"#;
+
+/// `--progress-template` for post-processing: one JSON object per hook call.
+pub const YT_DLP_POSTPROCESS_TEMPLATE: &str =
+    r#"postprocess:{"type":"postprocess","progress":%(progress)j}"#;
+
+/// `--print` template emitting the resolved output filename as a JSON string.
+pub const YT_DLP_FILENAME_TEMPLATE: &str = "%(filename)j";
+
+/// `--print` template emitting the final file path, title, uploader and duration after the file is moved into place.
+pub const YT_DLP_FILEPATH_TEMPLATE: &str =
+    r#"after_move:{"type":"file","filepath":%(filepath)j,"title":%(title)j,"uploader":%(uploader)j,"duration":%(duration)j}"#;
+
+// =============================================================================
+// yt-dlp Error Classification
+// =============================================================================
+
+// Lowercase substrings of yt-dlp stderr, checked by `AppError::from_yt_dlp`.
+
+/// Out of disk space while downloading or converting.
+pub const YT_DLP_DISK_FULL: &[&str] = &[
+    "no space left on device",
+    "not enough space on the disk",
+    "[errno 28]",
+    "[winerror 112]",
+];
+
+/// FFmpeg/aria2c could not be found.
+pub const YT_DLP_ENGINE_MISSING: &[&str] = &[
+    "ffmpeg not found",
+    "ffprobe not found",
+    "ffprobe and ffmpeg not found",
+    "ffmpeg is not installed",
+    "aria2c not found",
+    "aria2c is not installed",
+];
+
+/// FFmpeg conversion, merging or tagging failed.
+pub const YT_DLP_POST_PROCESSING: &[&str] = &["postprocessing:", "conversion failed", "ffmpeg exited with code"];
+
+/// The site throttled or bot-checked the request.
+pub const YT_DLP_RATE_LIMITED: &[&str] = &[
+    "http error 429",
+    "too many requests",
+    "rate-limit",
+    "rate limit",
+    "confirm you're not a bot",
+    "confirm you\u{2019}re not a bot",
+];
+
+/// The content is not available in the current region.
+pub const YT_DLP_GEO_BLOCKED: &[&str] = &[
+    "available in your country",
+    "not available from your location",
+    "blocked it in your country",
+    "geo restricted",
+    "geo-restricted",
+    "geo restriction",
+];
+
+/// The content requires age verification (sign-in).
+pub const YT_DLP_AGE_RESTRICTED: &[&str] = &[
+    "confirm your age",
+    "age-restricted",
+    "age restricted",
+    "inappropriate for some users",
+];
+
+/// The content is private, removed or otherwise unavailable.
+pub const YT_DLP_UNAVAILABLE: &[&str] = &[
+    "video unavailable",
+    "this video is unavailable",
+    "private video",
+    "has been removed",
+    "no longer available",
+    "members-only",
+    "http error 404",
+    "http error 410",
+];
+
+/// No extractor handles the URL.
+pub const YT_DLP_UNSUPPORTED_URL: &[&str] = &["unsupported url", "is not a valid url"];
+
+// =============================================================================
+// Output Templates
+// =============================================================================
+
+/// Default filename template, relative to the output directory and without extension.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "%(title)s";
+
+// =============================================================================
+// Audio Output Formats
+// =============================================================================
+
+/// MP3 encoding mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mp3Quality {
+    /// Constant bitrate in kbit/s (128, 192, 256 or 320).
+    Cbr(u32),
+    /// LAME VBR level, 0 (best) to 9 (smallest).
+    Vbr(u8),
+}
+
+/// Audio output format with per-format quality settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum AudioFormat {
+    Mp3 { quality: Mp3Quality },
+    /// Lossless FLAC; compression level 0 (fastest) to 12 (smallest).
+    Flac { compression_level: u8 },
+    Opus { bitrate_kbps: u32 },
+    /// AAC in an M4A container.
+    M4a { bitrate_kbps: u32 },
+    Wav,
+    /// Keep the best original audio stream without re-encoding.
+    Best,
+}
+
+impl Default for AudioFormat {
+    fn default() -> Self {
+        AudioFormat::Mp3 {
+            quality: Mp3Quality::Cbr(320),
+        }
+    }
+}
+
+/// Allowed MP3 constant bitrates in kbit/s.
+const MP3_CBR_BITRATES: &[u32] = &[128, 192, 256, 320];
+
+/// Allowed bitrate range for lossy Opus/AAC output in kbit/s.
+const LOSSY_BITRATE_RANGE: std::ops::RangeInclusive<u32> = 32..=512;
+
+impl AudioFormat {
+    /// Validate the per-format quality settings.
+    pub fn validate(&self) -> Result<(), AppError> {
+        match self {
+            AudioFormat::Mp3 {
+                quality: Mp3Quality::Cbr(kbps),
+            } if !MP3_CBR_BITRATES.contains(kbps) => Err(AppError::logic(format!(
+                "Unsupported MP3 bitrate: {}k (expected one of 128k, 192k, 256k, 320k).",
+                kbps
+            ))),
+            AudioFormat::Mp3 {
+                quality: Mp3Quality::Vbr(level),
+            } if *level > 9 => Err(AppError::logic(format!(
+                "MP3 VBR level must be between 0 and 9, got {}.",
+                level
+            ))),
+            AudioFormat::Flac { compression_level } if *compression_level > 12 => {
+                Err(AppError::logic(format!(
+                    "FLAC compression level must be between 0 and 12, got {}.",
+                    compression_level
+                )))
+            }
+            AudioFormat::Opus { bitrate_kbps } | AudioFormat::M4a { bitrate_kbps }
+                if !LOSSY_BITRATE_RANGE.contains(bitrate_kbps) =>
+            {
+                Err(AppError::logic(format!(
+                    "Bitrate must be between {}k and {}k, got {}k.",
+                    LOSSY_BITRATE_RANGE.start(),
+                    LOSSY_BITRATE_RANGE.end(),
+                    bitrate_kbps
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// File extension of the converted audio; `None` when the original stream is kept.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            AudioFormat::Mp3 { .. } => Some("mp3"),
+            AudioFormat::Flac { .. } => Some("flac"),
+            AudioFormat::Opus { .. } => Some("opus"),
+            AudioFormat::M4a { .. } => Some("m4a"),
+            AudioFormat::Wav => Some("wav"),
+            AudioFormat::Best => None,
+        }
+    }
+
+    /// Build the yt-dlp audio extraction arguments for this format.
+    pub fn yt_dlp_args(&self) -> Vec<String> {
+        let mut args = vec!["--extract-audio".to_string(), "--audio-format".to_string()];
+
+        match self {
+            AudioFormat::Mp3 { quality } => {
+                args.push("mp3".to_string());
+                args.push("--audio-quality".to_string());
+                args.push(match quality {
+                    Mp3Quality::Cbr(kbps) => format!("{}K", kbps),
+                    Mp3Quality::Vbr(level) => level.to_string(),
+                });
+            }
+            AudioFormat::Flac { compression_level } => {
+                args.push("flac".to_string());
+                args.push("--postprocessor-args".to_string());
+                args.push(format!("ExtractAudio:-compression_level {}", compression_level));
+            }
+            AudioFormat::Opus { bitrate_kbps } => {
+                args.push("opus".to_string());
+                args.push("--audio-quality".to_string());
+                args.push(format!("{}K", bitrate_kbps));
+            }
+            AudioFormat::M4a { bitrate_kbps } => {
+                args.push("m4a".to_string());
+                args.push("--audio-quality".to_string());
+                args.push(format!("{}K", bitrate_kbps));
+            }
+            AudioFormat::Wav => args.push("wav".to_string()),
+            AudioFormat::Best => args.push("best".to_string()),
+        }
+
+        args
+    }
+}
+
+// =============================================================================
+// Video Output Settings
+// =============================================================================
+
+/// Preferred video codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoCodec {
+    H264,
+    Vp9,
+    Av1,
+}
+
+impl VideoCodec {
+    /// yt-dlp format sort value for this codec.
+    fn sort_value(self) -> &'static str {
+        match self {
+            VideoCodec::H264 => "h264",
+            VideoCodec::Vp9 => "vp9",
+            VideoCodec::Av1 => "av01",
+        }
+    }
+}
+
+/// Output container for merged video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoContainer {
+    Mp4,
+    Mkv,
+    Webm,
+}
+
+impl VideoContainer {
+    pub fn extension(self) -> &'static str {
+        match self {
+            VideoContainer::Mp4 => "mp4",
+            VideoContainer::Mkv => "mkv",
+            VideoContainer::Webm => "webm",
+        }
+    }
+}
+
+/// Video download settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoOptions {
+    /// Maximum vertical resolution (e.g. 1080); `None` for best available.
+    pub max_height: Option<u32>,
+    /// Preferred codec; other codecs are used if it is unavailable.
+    pub codec: Option<VideoCodec>,
+    pub container: VideoContainer,
+    /// Maximum frame rate; `None` for no limit.
+    pub max_fps: Option<u32>,
+}
+
+impl VideoOptions {
+    /// Validate the video settings.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.max_height == Some(0) {
+            return Err(AppError::logic("Maximum resolution must be greater than 0."));
+        }
+
+        if self.max_fps == Some(0) {
+            return Err(AppError::logic("Maximum frame rate must be greater than 0."));
+        }
+
+        if self.container == VideoContainer::Webm && self.codec == Some(VideoCodec::H264) {
+            return Err(AppError::logic("H.264 video cannot be stored in a WebM container."));
+        }
+
+        Ok(())
+    }
+
+    /// Build the yt-dlp format selection and merge arguments.
+    ///
+    /// Hard limits (resolution, frame rate) go into the `-f` selector so they
+    /// are never exceeded; codec and container are `-S` preferences so the
+    /// download still succeeds when the source lacks them.
+    pub fn yt_dlp_args(&self) -> Vec<String> {
+        let mut filters = String::new();
+        if let Some(height) = self.max_height {
+            filters.push_str(&format!("[height<={}]", height));
+        }
+        if let Some(fps) = self.max_fps {
+            filters.push_str(&format!("[fps<={}]", fps));
+        }
+
+        let mut sort = Vec::new();
+        if let Some(height) = self.max_height {
+            sort.push(format!("res:{}", height));
+        }
+        if let Some(fps) = self.max_fps {
+            sort.push(format!("fps:{}", fps));
+        }
+        if let Some(codec) = self.codec {
+            sort.push(format!("vcodec:{}", codec.sort_value()));
+        }
+        // MKV holds any codec, so only MP4/WebM need container-friendly streams
+        match self.container {
+            VideoContainer::Mp4 => sort.push("ext:mp4:m4a".to_string()),
+            VideoContainer::Webm => sort.push("ext:webm:webm".to_string()),
+            VideoContainer::Mkv => {}
+        }
+
+        let mut args = vec!["-f".to_string(), format!("bv*{0}+ba/b{0}", filters)];
+        if !sort.is_empty() {
+            args.push("-S".to_string());
+            args.push(sort.join(","));
+        }
+        args.push("--merge-output-format".to_string());
+        args.push(self.container.extension().to_string());
+
+        args
+    }
+}
+
 // =============================================================================
-// Audio Quality Settings
+// Metadata Tagging
 // =============================================================================
 
-/// Map quality string to exact bitrate for yt-dlp/FFmpeg.
-pub fn get_audio_bitrate(quality: &str) -> &'static str {
-    match quality {
-        "128k" => "128K",
-        "192k" => "192K",
-        "256k" => "256K",
-        "320k" => "320K",
-        _ => "320K", // Default to highest quality
+/// FFmpeg arguments that center-crop the cover art to a square JPEG.
+const SQUARE_THUMBNAIL_PPA: &str = "ThumbnailsConvertor+ffmpeg_o:-c:v mjpeg -qmin 1 -qscale:v 1 \
+     -vf crop=\"'if(gt(ih,iw),iw,ih)':'if(gt(iw,ih),ih,iw)'\"";
+
+/// Per-job tag values that replace whatever the source provides.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagOverrides {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+}
+
+impl TagOverrides {
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.artist.is_none()
+            && self.album.is_none()
+            && self.track_number.is_none()
+    }
+}
+
+/// Tag (ID3/Vorbis/MP4) and cover art embedding settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagOptions {
+    #[serde(default)]
+    pub embed_metadata: bool,
+    #[serde(default)]
+    pub embed_thumbnail: bool,
+    /// Crop the embedded cover art to a square (album art style).
+    #[serde(default)]
+    pub square_thumbnail: bool,
+    /// yt-dlp `--parse-metadata` rules in `FROM:TO` form.
+    #[serde(default)]
+    pub parse_metadata: Vec<String>,
+    #[serde(default)]
+    pub overrides: TagOverrides,
+}
+
+impl TagOptions {
+    /// Validate the tagging settings.
+    ///
+    /// `audio_format` is the format of an audio download and
+    /// `video_container` the container of a video download; the other is `None`.
+    pub fn validate(
+        &self,
+        audio_format: Option<&AudioFormat>,
+        video_container: Option<VideoContainer>,
+    ) -> Result<(), AppError> {
+        // WAV has no standard cover art frame; yt-dlp refuses to embed one
+        if self.embed_thumbnail && matches!(audio_format, Some(AudioFormat::Wav)) {
+            return Err(AppError::logic("Cover art cannot be embedded in WAV files."));
+        }
+
+        // yt-dlp only embeds thumbnails in MP4/M4A, MKV, MP3, FLAC and Ogg files
+        if self.embed_thumbnail && video_container == Some(VideoContainer::Webm) {
+            return Err(AppError::logic(
+                "Cover art cannot be embedded in WebM videos. Choose MP4 or MKV instead.",
+            ));
+        }
+
+        for rule in &self.parse_metadata {
+            match rule.split_once(':') {
+                Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {}
+                _ => {
+                    return Err(AppError::logic(format!(
+                        "Invalid metadata rule (expected FROM:TO): {}",
+                        rule
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the yt-dlp tagging arguments.
+    ///
+    /// Overrides are written as FFmpeg `-metadata` output arguments of the
+    /// Metadata post-processor, so they win over the values yt-dlp embeds.
+    pub fn yt_dlp_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        for rule in &self.parse_metadata {
+            args.push("--parse-metadata".to_string());
+            args.push(rule.clone());
+        }
+
+        if self.embed_metadata || !self.overrides.is_empty() {
+            args.push("--embed-metadata".to_string());
+        }
+
+        if !self.overrides.is_empty() {
+            let overrides = &self.overrides;
+            let tags = [
+                ("title", overrides.title.clone()),
+                ("artist", overrides.artist.clone()),
+                ("album", overrides.album.clone()),
+                ("track", overrides.track_number.map(|n| n.to_string())),
+            ];
+
+            let metadata_args: Vec<String> = tags
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    value.map(|v| format!("-metadata {}", shlex_quote(&format!("{}={}", key, v))))
+                })
+                .collect();
+
+            args.push("--postprocessor-args".to_string());
+            args.push(format!("Metadata+ffmpeg_o:{}", metadata_args.join(" ")));
+        }
+
+        if self.embed_thumbnail {
+            args.push("--embed-thumbnail".to_string());
+
+            if self.square_thumbnail {
+                args.push("--convert-thumbnails".to_string());
+                args.push("jpg".to_string());
+                args.push("--postprocessor-args".to_string());
+                args.push(SQUARE_THUMBNAIL_PPA.to_string());
+            }
+        }
+
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_cover_art_in_wav() {
+        let tags = TagOptions {
+            embed_thumbnail: true,
+            ..TagOptions::default()
+        };
+
+        assert!(matches!(tags.validate(Some(&AudioFormat::Wav), None), Err(AppError::Logic(_))));
+        assert!(tags.validate(Some(&AudioFormat::default()), None).is_ok());
+        assert!(TagOptions::default().validate(Some(&AudioFormat::Wav), None).is_ok());
+    }
+
+    #[test]
+    fn rejects_cover_art_in_webm() {
+        let tags = TagOptions {
+            embed_thumbnail: true,
+            ..TagOptions::default()
+        };
+
+        assert!(matches!(
+            tags.validate(None, Some(VideoContainer::Webm)),
+            Err(AppError::Logic(_))
+        ));
+        for container in [VideoContainer::Mp4, VideoContainer::Mkv] {
+            assert!(tags.validate(None, Some(container)).is_ok(), "rejected {:?}", container);
+        }
+        assert!(TagOptions::default().validate(None, Some(VideoContainer::Webm)).is_ok());
     }
 }
@@ -6,6 +6,11 @@
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::config::{
+    YT_DLP_AGE_RESTRICTED, YT_DLP_DISK_FULL, YT_DLP_ENGINE_MISSING, YT_DLP_GEO_BLOCKED, YT_DLP_POST_PROCESSING,
+    YT_DLP_RATE_LIMITED, YT_DLP_UNAVAILABLE, YT_DLP_UNSUPPORTED_URL,
+};
+
 /// Application-wide error type that can be serialized to JSON for frontend consumption.
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -23,6 +28,49 @@ pub enum AppError {
 
     #[error("{0}")]
     Logic(String),
+
+    // === Downloader ===
+    #[error("{0}")]
+    UnsupportedUrl(String),
+
+    #[error("{0}")]
+    GeoBlocked(String),
+
+    #[error("{0}")]
+    AgeRestricted(String),
+
+    #[error("{0}")]
+    VideoUnavailable(String),
+
+    #[error("{0}")]
+    RateLimited(String),
+
+    #[error("{0}")]
+    EngineMissing(String),
+
+    #[error("{0}")]
+    PostProcessingFailed(String),
+
+    #[error("{0}")]
+    DiskFull(String),
+
+    #[error("{0}")]
+    DownloadFailed(String),
+
+    // === Engine Update ===
+    #[error("Integrity check failed: {0}")]
+    IntegrityCheckFailed(String),
+
+    // === Input Validation ===
+    #[error("{0}")]
+    InvalidUrl(String),
+
+    #[error("{0}")]
+    InvalidOutputPath(String),
+
+    // === Authentication ===
+    #[error("Secret store error: {0}")]
+    SecretStoreFailed(String),
 }
 
 /// JSON-serializable error response for the frontend.
@@ -43,6 +91,19 @@ impl Serialize for AppError {
             AppError::Zip(e) => ("ZIP_ERROR".to_string(), e.to_string()),
             AppError::Tauri(msg) => ("TAURI_ERROR".to_string(), msg.clone()),
             AppError::Logic(msg) => ("LOGIC_ERROR".to_string(), msg.clone()),
+            AppError::UnsupportedUrl(msg) => ("UNSUPPORTED_URL".to_string(), msg.clone()),
+            AppError::GeoBlocked(msg) => ("GEO_BLOCKED".to_string(), msg.clone()),
+            AppError::AgeRestricted(msg) => ("AGE_RESTRICTED".to_string(), msg.clone()),
+            AppError::VideoUnavailable(msg) => ("VIDEO_UNAVAILABLE".to_string(), msg.clone()),
+            AppError::RateLimited(msg) => ("RATE_LIMITED".to_string(), msg.clone()),
+            AppError::EngineMissing(msg) => ("ENGINE_MISSING".to_string(), msg.clone()),
+            AppError::PostProcessingFailed(msg) => ("POST_PROCESSING_FAILED".to_string(), msg.clone()),
+            AppError::DiskFull(msg) => ("DISK_FULL".to_string(), msg.clone()),
+            AppError::DownloadFailed(msg) => ("DOWNLOAD_FAILED".to_string(), msg.clone()),
+            AppError::IntegrityCheckFailed(msg) => ("INTEGRITY_CHECK_FAILED".to_string(), msg.clone()),
+            AppError::InvalidUrl(msg) => ("INVALID_URL".to_string(), msg.clone()),
+            AppError::InvalidOutputPath(msg) => ("INVALID_OUTPUT_PATH".to_string(), msg.clone()),
+            AppError::SecretStoreFailed(msg) => ("SECRET_STORE_FAILED".to_string(), msg.clone()),
         };
 
         ErrorResponse { code, message }.serialize(serializer)
@@ -59,7 +120,135 @@ impl AppError {
     pub fn tauri<T: ToString>(msg: T) -> Self {
         AppError::Tauri(msg.to_string())
     }
+
+    /// Classify a failed yt-dlp run from its stderr output.
+    ///
+    /// The message is the first `ERROR:` line (without the prefix), falling
+    /// back to the exit code. Unrecognized failures become `DownloadFailed`.
+    pub fn from_yt_dlp(stderr: &str, exit_code: Option<i32>) -> Self {
+        let message = stderr
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("ERROR:"))
+            .map(|line| line.trim().to_string())
+            .unwrap_or_else(|| match exit_code {
+                Some(code) => format!("yt-dlp exited with code {}", code),
+                None => "yt-dlp was terminated".to_string(),
+            });
+
+        let haystack = stderr.to_lowercase();
+        let matches = |patterns: &[&str]| patterns.iter().any(|pattern| haystack.contains(pattern));
+
+        // Most specific first: disk and engine problems surface inside post-processing errors
+        if matches(YT_DLP_DISK_FULL) {
+            AppError::DiskFull(message)
+        } else if matches(YT_DLP_ENGINE_MISSING) {
+            AppError::EngineMissing(message)
+        } else if matches(YT_DLP_POST_PROCESSING) {
+            AppError::PostProcessingFailed(message)
+        } else if matches(YT_DLP_RATE_LIMITED) {
+            AppError::RateLimited(message)
+        } else if matches(YT_DLP_GEO_BLOCKED) {
+            AppError::GeoBlocked(message)
+        } else if matches(YT_DLP_AGE_RESTRICTED) {
+            AppError::AgeRestricted(message)
+        } else if matches(YT_DLP_UNAVAILABLE) {
+            AppError::VideoUnavailable(message)
+        } else if matches(YT_DLP_UNSUPPORTED_URL) {
+            AppError::UnsupportedUrl(message)
+        } else {
+            AppError::DownloadFailed(message)
+        }
+    }
 }
 
 // Note: AppError implements Serialize, so Tauri automatically converts it
 // to InvokeError via its blanket impl: `impl<T: Serialize> From<T> for InvokeError`
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_yt_dlp_errors() {
+        let samples = [
+            (
+                "ERROR: unable to write data: [Errno 28] No space left on device",
+                "DISK_FULL",
+            ),
+            (
+                "WARNING: You have requested merging of multiple formats but ffmpeg is not installed. Aborting due to --abort-on-error\n\
+                 ERROR: You have requested merging of multiple formats but ffmpeg is not installed. Aborting due to --abort-on-error",
+                "ENGINE_MISSING",
+            ),
+            (
+                "ERROR: Postprocessing: audio conversion failed: Error opening output files: Invalid argument",
+                "POST_PROCESSING_FAILED",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Unable to download API page: HTTP Error 429: Too Many Requests",
+                "RATE_LIMITED",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Sign in to confirm you\u{2019}re not a bot. Use --cookies-from-browser or --cookies for the authentication.",
+                "RATE_LIMITED",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Video unavailable. The uploader has not made this video available in your country",
+                "GEO_BLOCKED",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Sign in to confirm your age. This video may be inappropriate for some users.",
+                "AGE_RESTRICTED",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Private video. Sign in if you've been granted access to this video",
+                "VIDEO_UNAVAILABLE",
+            ),
+            (
+                "ERROR: [youtube] dQw4w9WgXcQ: Video unavailable. This video has been removed by the uploader",
+                "VIDEO_UNAVAILABLE",
+            ),
+            (
+                "ERROR: Unsupported URL: https://example.com/page",
+                "UNSUPPORTED_URL",
+            ),
+            (
+                "ERROR: 'not-a-url' is not a valid URL. Set --default-search \"ytsearch\" (or run  yt-dlp \"ytsearch:not-a-url\" ) to search YouTube",
+                "UNSUPPORTED_URL",
+            ),
+        ];
+
+        for (stderr, code) in samples {
+            let error = serde_json::to_value(AppError::from_yt_dlp(stderr, Some(1))).unwrap();
+            assert_eq!(error["code"], code, "classified {:?}", stderr);
+        }
+    }
+
+    #[test]
+    fn message_is_the_first_error_line() {
+        let stderr = "WARNING: [youtube] Falling back to generic n function search\n\
+                      ERROR: Unsupported URL: https://example.com/page\n\
+                      ERROR: second error";
+
+        let error = AppError::from_yt_dlp(stderr, Some(1));
+        assert!(matches!(
+            error,
+            AppError::UnsupportedUrl(ref message) if message == "Unsupported URL: https://example.com/page"
+        ));
+    }
+
+    #[test]
+    fn unknown_failures_fall_back_to_download_failed() {
+        let error = AppError::from_yt_dlp("ERROR: [generic] Unable to extract title", Some(1));
+        assert!(matches!(
+            error,
+            AppError::DownloadFailed(ref message) if message == "[generic] Unable to extract title"
+        ));
+
+        let error = AppError::from_yt_dlp("", Some(2));
+        assert!(matches!(error, AppError::DownloadFailed(ref message) if message == "yt-dlp exited with code 2"));
+
+        let error = AppError::from_yt_dlp("", None);
+        assert!(matches!(error, AppError::DownloadFailed(ref message) if message == "yt-dlp was terminated"));
+    }
+}
@@ -13,7 +13,9 @@ mod config;
 mod error;
 mod utils;
 
-use commands::{app_update, downloader, engine, files};
+use commands::{app_update, auth, control, downloader, engine, files, history, playlist, probe, queue, settings};
+use tauri::Manager;
+use utils::secrets;
 
 /// Application entry point.
 ///
@@ -25,6 +27,26 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_opener::init())
+        // === State ===
+        .setup(|app| {
+            let handle = app.handle();
+            // Both secret stores share one key; if it cannot be created they report it when used
+            let _ = secrets::init_app_secret_key(handle);
+            let (saved_settings, settings_warnings) = settings::load_settings(handle);
+            app.manage(settings::SettingsState::new(
+                saved_settings,
+                settings_warnings,
+                settings::UpdateSourceOverrides::from_env(),
+            ));
+            app.manage(auth::AuthState::new(auth::load_auth_profiles(handle)));
+            app.manage(control::ProcessRegistry::default());
+            app.manage(app_update::AppUpdateState::default());
+            app.manage(history::HistoryState::default());
+            let (saved_queue, queue_warnings) = queue::load_queue(handle);
+            app.manage(queue::QueueState::new(saved_queue, queue_warnings));
+            queue::pump_queue(handle);
+            Ok(())
+        })
         // === Command Handlers ===
         .invoke_handler(tauri::generate_handler![
             // App Update
@@ -32,10 +54,41 @@ pub fn run() {
             app_update::install_app_update,
             // Engine Management
             engine::install_engine_update,
+            engine::rollback_engine,
+            engine::get_engine_status,
+            engine::engine_updates_enabled,
+            engine::check_engine_update,
+            engine::update_engine,
             // File Operations
             files::show_in_folder,
             // Download
             downloader::download_video,
+            probe::probe_url,
+            probe::preview_output_template,
+            control::cancel_download,
+            control::pause_download,
+            control::resume_download,
+            // Download Queue
+            queue::get_queue,
+            queue::enqueue_downloads,
+            queue::dequeue_download,
+            queue::reorder_queue,
+            queue::set_queue_workers,
+            // Download History
+            history::list_history,
+            history::search_history,
+            history::delete_history_entry,
+            history::redownload_history_entry,
+            // Settings
+            settings::get_settings,
+            settings::update_settings,
+            // Auth Profiles
+            auth::list_auth_profiles,
+            auth::save_auth_profile,
+            auth::delete_auth_profile,
+            // Playlists
+            playlist::expand_playlist,
+            playlist::enqueue_playlist,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,343 @@
+//! HTTP download utilities.
+//!
+//! Streams update downloads straight to disk with progress events,
+//! resumes interrupted downloads with HTTP Range requests (guarded by
+//! `If-Range`, so a changed file is never spliced onto an old partial),
+//! and enforces a maximum download size.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{StatusCode, Url};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::config::{EVENT_HTTP_DOWNLOAD_PROGRESS, USER_AGENT};
+use crate::error::AppError;
+
+/// Minimum time between two progress events for the same download.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Byte-level progress of an HTTP download.
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgressEvent {
+    /// Identifies the download, e.g. `engine` or `app-update`.
+    pub download_id: String,
+    pub downloaded_bytes: u64,
+    /// `None` if the server did not report a size.
+    pub total_bytes: Option<u64>,
+}
+
+/// Fail on a non-success HTTP status.
+pub fn check_status(response: reqwest::Response) -> Result<reqwest::Response, AppError> {
+    if !response.status().is_success() {
+        return Err(AppError::logic(format!(
+            "Download failed with status: {} - {}",
+            response.status(),
+            response
+                .status()
+                .canonical_reason()
+                .unwrap_or("Unknown error")
+        )));
+    }
+
+    Ok(response)
+}
+
+/// Derive a safe local file name from the last path segment of a URL.
+///
+/// Returns `None` if the segment is empty or contains anything other than
+/// ASCII letters, digits, `.`, `-` and `_`.
+pub fn file_name_from_url(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let name = path.rsplit('/').next()?;
+
+    let is_safe = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+
+    is_safe.then(|| name.to_string())
+}
+
+/// Append `suffix` to the last path segment of a URL.
+///
+/// The query string and fragment are kept, so
+/// `https://host/engine.zip?token=1` becomes
+/// `https://host/engine.zip.manifest.json?token=1`.
+pub fn with_file_suffix(url: &str, suffix: &str) -> Result<String, AppError> {
+    let mut parsed = Url::parse(url).map_err(|e| AppError::logic(format!("Invalid URL {}: {}", url, e)))?;
+
+    let last = parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::logic(format!("URL does not name a file: {}", url)))?;
+
+    parsed
+        .path_segments_mut()
+        .map_err(|_| AppError::logic(format!("URL does not name a file: {}", url)))?
+        .pop()
+        .push(&format!("{}{}", last, suffix));
+
+    Ok(parsed.into())
+}
+
+/// Fetch a small document into memory, up to `max_bytes`.
+///
+/// The reported length is checked before reading, and the bytes actually
+/// received are counted, so a server cannot exhaust memory either way.
+pub async fn fetch_capped(client: &reqwest::Client, url: &str, max_bytes: u64) -> Result<Vec<u8>, AppError> {
+    let mut response = check_status(client.get(url).send().await?)?;
+    let too_large = || AppError::logic(format!("Response is too large: over {} KB.", max_bytes / 1024));
+
+    if response.content_length().is_some_and(|length| length > max_bytes) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() as u64 + chunk.len() as u64 > max_bytes {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Download a URL to a file, streaming chunks to disk.
+///
+/// Data is written to `<destination>.part` and renamed into place once
+/// complete. The response's ETag or Last-Modified date is kept in
+/// `<destination>.part.validator`; if a `.part` file is left over from an
+/// interrupted attempt, the download resumes from where it stopped with
+/// that validator in `If-Range`. The partial file is discarded when it has
+/// no validator, when the server sends the whole file (it changed, or the
+/// server ignores Range) or when the resumed response has another
+/// validator. A partial file the server reports as complete (416) is only
+/// kept if the response carries the same validator. A download that exceeds `max_bytes` is aborted and its
+/// partial file removed.
+///
+/// # Arguments
+/// * `app` - App handle used to emit `EVENT_HTTP_DOWNLOAD_PROGRESS`
+/// * `client` - HTTP client to download with
+/// * `url` - Source URL
+/// * `destination` - Final file path (replaced if it exists)
+/// * `max_bytes` - Largest accepted download size
+/// * `download_id` - Identifier carried by the progress events
+///
+/// # Returns
+/// * `Ok(u64)` with the size of the downloaded file
+/// * `Err(AppError)` on network, I/O or size-limit failure
+pub async fn download_to_file(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    destination: &Path,
+    max_bytes: u64,
+    download_id: &str,
+) -> Result<u64, AppError> {
+    let partial = partial_path(destination);
+    if destination.exists() {
+        fs::remove_file(destination)?;
+    }
+
+    // Without a validator there is no way to tell whether the partial file is still current
+    let validator_file = validator_path(&partial);
+    let validator = fs::read_to_string(&validator_file)
+        .ok()
+        .filter(|validator| !validator.is_empty());
+    let mut offset = match validator {
+        Some(_) => fs::metadata(&partial).map(|meta| meta.len()).unwrap_or(0),
+        None => 0,
+    };
+
+    let mut response = loop {
+        let mut request = client.get(url).header("User-Agent", USER_AGENT);
+        if let Some(validator) = validator.as_deref().filter(|_| offset > 0) {
+            request = request
+                .header(RANGE, format!("bytes={}-", offset))
+                .header(IF_RANGE, validator);
+        }
+        let response = request.send().await?;
+
+        if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+            // The partial file is already complete, or no longer matches the remote file.
+            // A 416 ignores If-Range, so the size alone cannot prove the partial is current
+            if response_validator(&response) == validator && content_range_total(&response) == Some(offset) {
+                fs::rename(&partial, destination)?;
+                let _ = fs::remove_file(&validator_file);
+                emit_progress(app, download_id, offset, Some(offset));
+                return Ok(offset);
+            }
+
+            fs::remove_file(&partial)?;
+            offset = 0;
+            continue;
+        }
+
+        if response.status() == StatusCode::PARTIAL_CONTENT && response_validator(&response) != validator {
+            // The server resumed a different version of the file
+            fs::remove_file(&partial)?;
+            offset = 0;
+            continue;
+        }
+
+        break check_status(response)?;
+    };
+
+    // A plain 200 means the file changed or the server ignored the Range
+    // header; the partial file is replaced along with its validator
+    if response.status() != StatusCode::PARTIAL_CONTENT {
+        offset = 0;
+        match response_validator(&response) {
+            Some(validator) => fs::write(&validator_file, validator)?,
+            None => {
+                let _ = fs::remove_file(&validator_file);
+            }
+        }
+    }
+
+    let total_bytes = match response.status() {
+        StatusCode::PARTIAL_CONTENT => content_range_total(&response)
+            .or_else(|| response.content_length().map(|length| length + offset)),
+        _ => response.content_length(),
+    };
+
+    if let Some(total) = total_bytes.filter(|total| *total > max_bytes) {
+        discard_partial(&partial);
+        return Err(size_limit_error(total, max_bytes));
+    }
+
+    let mut file = if offset > 0 {
+        OpenOptions::new().append(true).open(&partial)?
+    } else {
+        File::create(&partial)?
+    };
+
+    let mut downloaded = offset;
+    let mut last_emit = Instant::now();
+    emit_progress(app, download_id, downloaded, total_bytes);
+
+    while let Some(chunk) = response.chunk().await? {
+        downloaded += chunk.len() as u64;
+
+        if downloaded > max_bytes {
+            drop(file);
+            discard_partial(&partial);
+            return Err(size_limit_error(downloaded, max_bytes));
+        }
+
+        file.write_all(&chunk)?;
+
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit_progress(app, download_id, downloaded, total_bytes);
+            last_emit = Instant::now();
+        }
+    }
+
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&partial, destination)?;
+    let _ = fs::remove_file(&validator_file);
+    emit_progress(app, download_id, downloaded, total_bytes.or(Some(downloaded)));
+
+    Ok(downloaded)
+}
+
+/// Path of the in-progress file for a download destination.
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    destination.with_file_name(name)
+}
+
+/// Path of the file holding the `If-Range` validator of a partial file.
+fn validator_path(partial: &Path) -> PathBuf {
+    let mut name = partial.file_name().unwrap_or_default().to_os_string();
+    name.push(".validator");
+    partial.with_file_name(name)
+}
+
+/// Remove a partial file together with its validator.
+fn discard_partial(partial: &Path) {
+    let _ = fs::remove_file(partial);
+    let _ = fs::remove_file(validator_path(partial));
+}
+
+/// Validator for `If-Range`: the ETag, or else the Last-Modified date.
+///
+/// Weak ETags (`W/"..."`) cannot be used with `If-Range` and are ignored.
+fn response_validator(response: &reqwest::Response) -> Option<String> {
+    let headers = response.headers();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(str::to_string)
+}
+
+/// Total size from a `Content-Range: bytes <range>/<total>` header.
+fn content_range_total(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn size_limit_error(size: u64, max_bytes: u64) -> AppError {
+    AppError::logic(format!(
+        "Download is too large: {} MB exceeds the {} MB limit.",
+        size / (1024 * 1024),
+        max_bytes / (1024 * 1024)
+    ))
+}
+
+fn emit_progress(app: &AppHandle, download_id: &str, downloaded_bytes: u64, total_bytes: Option<u64>) {
+    let _ = app.emit(
+        EVENT_HTTP_DOWNLOAD_PROGRESS,
+        DownloadProgressEvent {
+            download_id: download_id.to_string(),
+            downloaded_bytes,
+            total_bytes,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixes_the_file_name_and_keeps_the_query() {
+        let cases = [
+            ("https://host/engine.zip", "https://host/engine.zip.minisig"),
+            ("https://host/a/engine.zip?token=x%2Fy&v=2", "https://host/a/engine.zip.minisig?token=x%2Fy&v=2"),
+            ("https://host/index.json#latest", "https://host/index.json.minisig#latest"),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(with_file_suffix(url, ".minisig").unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_urls_without_a_file_name() {
+        for url in ["https://host/", "https://host/dir/", "mailto:someone@example.com", "not a url"] {
+            assert!(matches!(with_file_suffix(url, ".minisig"), Err(AppError::Logic(_))), "accepted {:?}", url);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! File integrity utilities.
+//!
+//! Provides SHA-256 hashing and minisign signature verification used to
+//! check downloaded engine packages before anything is installed.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use minisign_verify::{PublicKey, Signature};
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+
+/// Compute the SHA-256 digest of a file.
+///
+/// The file is streamed, so large archives are never held in memory.
+///
+/// # Arguments
+/// * `path` - File to hash
+///
+/// # Returns
+/// * `Ok(String)` with the lowercase hex digest
+/// * `Err(AppError)` if the file cannot be read
+pub fn sha256_file(path: &Path) -> Result<String, AppError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Check that a file matches an expected SHA-256 digest.
+///
+/// # Arguments
+/// * `path` - File to check
+/// * `expected` - Expected hex digest (case-insensitive)
+/// * `label` - Name used in the error message
+///
+/// # Returns
+/// * `Ok(())` if the digests match
+/// * `Err(AppError::IntegrityCheckFailed)` on a mismatch
+pub fn verify_sha256(path: &Path, expected: &str, label: &str) -> Result<(), AppError> {
+    let actual = sha256_file(path)?;
+
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(AppError::IntegrityCheckFailed(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            label,
+            expected.trim(),
+            actual
+        )));
+    }
+
+    Ok(())
+}
+
+/// Verify a minisign signature over `data`.
+///
+/// # Arguments
+/// * `data` - Signed content
+/// * `signature` - Contents of the `.minisig` file
+/// * `public_key` - Base64 minisign public key
+///
+/// # Returns
+/// * `Ok(())` if the signature is valid for the key
+/// * `Err(AppError::IntegrityCheckFailed)` otherwise
+pub fn verify_minisign(data: &[u8], signature: &str, public_key: &str) -> Result<(), AppError> {
+    let public_key = PublicKey::from_base64(public_key)
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Invalid signing public key: {}", e)))?;
+
+    let signature = Signature::decode(signature)
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Invalid signature file: {}", e)))?;
+
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| AppError::IntegrityCheckFailed(format!("Signature verification failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Public half of a throwaway keypair used only to sign the fixtures below.
+    const TEST_PUBLIC_KEY: &str = "RWTR8J6AEtlRwOZslZATDFC3iH1z3t7VZuNLghg0adJI62qpZGR6KZlq";
+
+    const MANIFEST: &str =
+        r#"{"archive_sha256":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","binaries":{}}"#;
+
+    /// `MANIFEST` signed with the test key.
+    const MANIFEST_SIGNATURE: &str = "untrusted comment: signature from godspeed test key
+RUTR8J6AEtlRwLQfrBUTAbHzTsUOngiXlFpKO63KnkYgZZ4UqZSBNw/KTQjTufun9ixOa2bIJINjiM472WqHENxkq9nKb13tggI=
+trusted comment: timestamp:1792191679\tfile:fixture.json\thashed
+gH0Hm5xPb0QMGTdndydSINiBPekLEmABMW2xL+gkNhgMqSWszlShy6htt4RVrFUfrta6Epd2hlIQ8An2kLavCw==
+";
+
+    #[test]
+    fn accepts_documents_signed_with_the_key() {
+        assert!(verify_minisign(MANIFEST.as_bytes(), MANIFEST_SIGNATURE, TEST_PUBLIC_KEY).is_ok());
+    }
+
+    #[test]
+    fn rejects_tampered_documents() {
+        let tampered = MANIFEST.replacen('a', "b", 1);
+
+        assert!(matches!(
+            verify_minisign(tampered.as_bytes(), MANIFEST_SIGNATURE, TEST_PUBLIC_KEY),
+            Err(AppError::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_signatures_from_other_keys() {
+        let other_key = "RWRb8I5mbJetCGykDqAH6TEAiu+XtIHJKod2Bp8JPSPCQAX6ZDd/8KYU";
+
+        assert!(matches!(
+            verify_minisign(MANIFEST.as_bytes(), MANIFEST_SIGNATURE, other_key),
+            Err(AppError::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_or_malformed_signatures() {
+        for signature in ["", "untrusted comment: nothing here\n", "<html>Not Found</html>"] {
+            assert!(
+                verify_minisign(MANIFEST.as_bytes(), signature, TEST_PUBLIC_KEY).is_err(),
+                "accepted {:?}",
+                signature
+            );
+        }
+    }
+}
@@ -1,3 +1,13 @@
 //! Utility modules for the Godspeed Downloader.
 
+pub mod http;
+pub mod integrity;
+pub mod network;
+pub mod paths;
+pub mod process;
+pub mod progress;
+pub mod secrets;
+pub mod shlex;
+pub mod template;
+pub mod validation;
 pub mod zip;
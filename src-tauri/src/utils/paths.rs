@@ -0,0 +1,54 @@
+//! App directory helpers.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+use crate::error::AppError;
+
+/// Resolve a file in the app data directory.
+///
+/// The directory is not created; callers writing the file create it first.
+///
+/// # Arguments
+/// * `app` - App handle used to resolve the directory
+/// * `name` - File name inside the app data directory
+///
+/// # Returns
+/// * `Ok(PathBuf)` with the file path
+/// * `Err(AppError)` if the app data directory cannot be resolved
+pub fn app_data_path(app: &AppHandle, name: &str) -> Result<PathBuf, AppError> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::tauri(format!("Failed to resolve app data directory: {}", e)))?;
+
+    Ok(data_dir.join(name))
+}
+
+/// Move an unreadable app file aside, keeping it as a backup.
+///
+/// The file is renamed to `<name>.bad`, or `<name>.bad.<n>` if earlier
+/// backups exist, so a fresh file can take its place without losing it.
+///
+/// # Arguments
+/// * `path` - File to move aside
+///
+/// # Returns
+/// * `Ok(PathBuf)` with the backup path
+/// * `Err(io::Error)` if the file cannot be renamed
+pub fn set_aside(path: &Path) -> io::Result<PathBuf> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let backup = (0..)
+        .map(|n| match n {
+            0 => path.with_file_name(format!("{}.bad", name)),
+            n => path.with_file_name(format!("{}.bad.{}", name, n)),
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_default();
+
+    fs::rename(path, &backup)?;
+    Ok(backup)
+}
@@ -0,0 +1,91 @@
+//! Process management utilities.
+//!
+//! Provides helpers for terminating a process together with the child
+//! processes it started (e.g. yt-dlp spawning aria2c and FFmpeg).
+
+use std::process::Command;
+
+use crate::error::AppError;
+
+/// Windows flag to prevent helper processes from flashing a console window.
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Kill a process and all of its descendants.
+///
+/// Platform-specific behavior:
+/// - Windows: Uses `taskkill /T /F` which walks the process tree itself
+/// - Unix: Collects descendants via `pgrep -P` before killing, since children
+///   are re-parented (and become untraceable) once their parent exits
+///
+/// # Arguments
+/// * `pid` - Process ID of the root process
+///
+/// # Returns
+/// * `Ok(())` if the root process was killed
+/// * `Err(AppError)` if the kill command could not be run or failed
+#[cfg(target_os = "windows")]
+pub fn kill_process_tree(pid: u32) -> Result<(), AppError> {
+    use std::os::windows::process::CommandExt;
+
+    let status = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()
+        .map_err(|e| AppError::logic(format!("Failed to run taskkill: {}", e)))?;
+
+    if !status.success() {
+        return Err(AppError::logic(format!(
+            "taskkill exited with code: {:?}",
+            status.code()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Kill a process and all of its descendants.
+///
+/// See the Windows variant for details.
+#[cfg(not(target_os = "windows"))]
+pub fn kill_process_tree(pid: u32) -> Result<(), AppError> {
+    let mut pids = vec![pid];
+    collect_descendants(pid, &mut pids);
+
+    let mut root_killed = false;
+
+    for (index, target) in pids.iter().enumerate() {
+        let status = Command::new("kill")
+            .args(["-9", &target.to_string()])
+            .status()
+            .map_err(|e| AppError::logic(format!("Failed to run kill: {}", e)))?;
+
+        if index == 0 {
+            root_killed = status.success();
+        }
+    }
+
+    if !root_killed {
+        return Err(AppError::logic(format!("Failed to kill process {}", pid)));
+    }
+
+    Ok(())
+}
+
+/// Recursively collect the PIDs of all descendants of a process.
+#[cfg(not(target_os = "windows"))]
+fn collect_descendants(pid: u32, pids: &mut Vec<u32>) {
+    let Ok(output) = Command::new("pgrep").args(["-P", &pid.to_string()]).output() else {
+        return;
+    };
+
+    let children: Vec<u32> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect();
+
+    for child in children {
+        pids.push(child);
+        collect_descendants(child, pids);
+    }
+}
@@ -0,0 +1,410 @@
+//! yt-dlp output parsing utilities.
+//!
+//! Turns the JSON lines printed by yt-dlp's `--progress-template` and
+//! `--print` options (and the aria2c external downloader summary) into
+//! typed progress events for the frontend.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Stage of a download job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressPhase {
+    Extracting,
+    Downloading,
+    PostProcessing,
+    Finished,
+}
+
+/// Structured progress update for a single download job.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressEvent {
+    pub job_id: String,
+    pub phase: ProgressPhase,
+    pub percent: Option<f64>,
+    pub downloaded_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    pub speed_bytes_per_sec: Option<u64>,
+    pub eta_secs: Option<u64>,
+    pub fragment_index: Option<u32>,
+    pub fragment_count: Option<u32>,
+}
+
+impl ProgressEvent {
+    /// Create an event for a phase with no progress details.
+    pub fn phase(job_id: &str, phase: ProgressPhase) -> Self {
+        Self {
+            job_id: job_id.to_string(),
+            phase,
+            percent: None,
+            downloaded_bytes: None,
+            total_bytes: None,
+            speed_bytes_per_sec: None,
+            eta_secs: None,
+            fragment_index: None,
+            fragment_count: None,
+        }
+    }
+}
+
+/// Raw output line from a download job, for terminal display.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEvent {
+    pub job_id: String,
+    pub line: String,
+}
+
+/// Completion notice for a download job.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompleteEvent {
+    pub job_id: String,
+    pub file_path: Option<String>,
+}
+
+/// A machine-readable line printed by yt-dlp.
+///
+/// Produced by the `--progress-template` and `--print` templates in
+/// `config.rs`; each line is a JSON object tagged with `type`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TemplateLine {
+    Download { progress: DownloadProgress },
+    Postprocess {},
+    File(FinishedFile),
+}
+
+/// yt-dlp download progress hook data.
+#[derive(Debug, Deserialize)]
+struct DownloadProgress {
+    downloaded_bytes: Option<f64>,
+    total_bytes: Option<f64>,
+    total_bytes_estimate: Option<f64>,
+    speed: Option<f64>,
+    eta: Option<f64>,
+    fragment_index: Option<u32>,
+    fragment_count: Option<u32>,
+    filename: Option<String>,
+}
+
+/// A file that finished downloading and post-processing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FinishedFile {
+    pub filepath: String,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub uploader: Option<String>,
+    /// Duration in seconds.
+    #[serde(default, deserialize_with = "number_or_none")]
+    pub duration: Option<f64>,
+}
+
+/// Accept a JSON number, treating yt-dlp's `"NA"` placeholder as missing.
+fn number_or_none<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    Ok(Value::deserialize(deserializer)?.as_f64())
+}
+
+/// A parsed line of yt-dlp/aria2c output.
+#[derive(Debug)]
+pub enum OutputLine {
+    /// Progress update; `filename` is the file being downloaded, if known.
+    Progress {
+        event: ProgressEvent,
+        filename: Option<String>,
+    },
+    /// A finished file (after all post-processing).
+    FinalFile(FinishedFile),
+    /// yt-dlp found the output file on disk and did not download it again.
+    AlreadyDownloaded(String),
+}
+
+/// Parse a single line of yt-dlp/aria2c output.
+///
+/// Returns `None` for plain log lines that carry no machine-readable data.
+pub fn parse_output_line(job_id: &str, line: &str) -> Option<OutputLine> {
+    let line = line.trim();
+
+    // aria2c summary: [#2089b0 1.2MiB/10MiB(12%) CN:16 DL:2.3MiB ETA:4s]
+    if line.starts_with("[#") {
+        return parse_aria2c_line(job_id, line).map(|event| OutputLine::Progress {
+            event,
+            filename: None,
+        });
+    }
+
+    // [download] /path/Title.mp3 has already been downloaded
+    if let Some(path) = line
+        .strip_prefix("[download] ")
+        .and_then(|rest| rest.strip_suffix(" has already been downloaded"))
+    {
+        return Some(OutputLine::AlreadyDownloaded(path.to_string()));
+    }
+
+    if !line.starts_with('{') {
+        return None;
+    }
+
+    match serde_json::from_str::<TemplateLine>(line).ok()? {
+        TemplateLine::Download { progress } => {
+            let total = progress.total_bytes.or(progress.total_bytes_estimate);
+
+            let mut event = ProgressEvent::phase(job_id, ProgressPhase::Downloading);
+            event.downloaded_bytes = progress.downloaded_bytes.map(|b| b as u64);
+            event.total_bytes = total.map(|b| b as u64);
+            event.speed_bytes_per_sec = progress.speed.map(|s| s as u64);
+            event.eta_secs = progress.eta.map(|e| e as u64);
+            event.fragment_index = progress.fragment_index;
+            event.fragment_count = progress.fragment_count;
+            event.percent = match (progress.downloaded_bytes, total) {
+                (Some(done), Some(total)) if total > 0.0 => Some((done / total * 100.0).min(100.0)),
+                _ => None,
+            };
+
+            Some(OutputLine::Progress {
+                event,
+                filename: progress.filename,
+            })
+        }
+        TemplateLine::Postprocess {} => Some(OutputLine::Progress {
+            event: ProgressEvent::phase(job_id, ProgressPhase::PostProcessing),
+            filename: None,
+        }),
+        TemplateLine::File(file) => Some(OutputLine::FinalFile(file)),
+    }
+}
+
+/// Parse an aria2c download summary line.
+///
+/// Example: `[#2089b0 1.2MiB/10MiB(12%) CN:16 DL:2.3MiB ETA:4s]`
+fn parse_aria2c_line(job_id: &str, line: &str) -> Option<ProgressEvent> {
+    let body = line.trim_start_matches('[').trim_end_matches(']');
+    let mut tokens = body.split_whitespace();
+
+    // Skip the GID
+    tokens.next()?;
+    let sizes = tokens.next()?;
+
+    let (transfer, percent) = match sizes.split_once('(') {
+        Some((transfer, percent)) => (transfer, percent.trim_end_matches("%)").parse().ok()),
+        None => (sizes, None),
+    };
+    let (downloaded, total) = transfer.split_once('/')?;
+
+    let mut event = ProgressEvent::phase(job_id, ProgressPhase::Downloading);
+    event.downloaded_bytes = parse_size(downloaded);
+    event.total_bytes = parse_size(total);
+    event.percent = percent;
+
+    for token in tokens {
+        if let Some(speed) = token.strip_prefix("DL:") {
+            event.speed_bytes_per_sec = parse_size(speed);
+        } else if let Some(eta) = token.strip_prefix("ETA:") {
+            event.eta_secs = parse_duration_units(eta);
+        }
+    }
+
+    Some(event)
+}
+
+/// Parse a human-readable size such as `10.50MiB`, `512KiB` or `3MB` into bytes.
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier: f64 = match unit {
+        "" | "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "KB" | "kB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+
+    Some((number * multiplier) as u64)
+}
+
+/// Parse an aria2c-style duration such as `4s`, `1m30s` or `1h2m3s` into seconds.
+fn parse_duration_units(value: &str) -> Option<u64> {
+    let mut total = 0u64;
+    let mut number = String::new();
+
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let amount: u64 = number.parse().ok()?;
+        number.clear();
+
+        total += match c {
+            'h' => amount * 3600,
+            'm' => amount * 60,
+            's' => amount,
+            _ => return None,
+        };
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn progress(line: &str) -> (ProgressEvent, Option<String>) {
+        match parse_output_line("job", line) {
+            Some(OutputLine::Progress { event, filename }) => (event, filename),
+            other => panic!("no progress for {:?}: {:?}", line, other),
+        }
+    }
+
+    fn parse(line: &str) -> ProgressEvent {
+        progress(line).0
+    }
+
+    #[test]
+    fn parses_download_template_lines() {
+        let (event, filename) = progress(
+            r#"{"type":"download","progress":{"status":"downloading","downloaded_bytes":2621440,"total_bytes":10485760,"speed":1048576.5,"eta":7.9,"fragment_index":5,"fragment_count":20,"filename":"/music/Title.webm","_percent_str":" 25.0%"}}"#,
+        );
+
+        assert_eq!(event.job_id, "job");
+        assert_eq!(event.phase, ProgressPhase::Downloading);
+        assert_eq!(event.percent, Some(25.0));
+        assert_eq!(event.downloaded_bytes, Some(2_621_440));
+        assert_eq!(event.total_bytes, Some(10 * MIB));
+        assert_eq!(event.speed_bytes_per_sec, Some(MIB));
+        assert_eq!(event.eta_secs, Some(7));
+        assert_eq!(event.fragment_index, Some(5));
+        assert_eq!(event.fragment_count, Some(20));
+        assert_eq!(filename.as_deref(), Some("/music/Title.webm"));
+    }
+
+    #[test]
+    fn falls_back_to_the_estimated_total() {
+        let event = parse(
+            r#"{"type":"download","progress":{"downloaded_bytes":512,"total_bytes":null,"total_bytes_estimate":2048}}"#,
+        );
+
+        assert_eq!(event.total_bytes, Some(2048));
+        assert_eq!(event.percent, Some(25.0));
+    }
+
+    #[test]
+    fn missing_fields_stay_empty() {
+        let event = parse(
+            r#"{"type":"download","progress":{"status":"downloading","downloaded_bytes":1024,"total_bytes":null,"speed":null,"eta":null}}"#,
+        );
+
+        assert_eq!(event.downloaded_bytes, Some(1024));
+        assert_eq!(event.total_bytes, None);
+        assert_eq!(event.percent, None);
+        assert_eq!(event.speed_bytes_per_sec, None);
+        assert_eq!(event.eta_secs, None);
+        assert_eq!(event.fragment_count, None);
+
+        // A zero total cannot give a percentage
+        assert_eq!(parse(r#"{"type":"download","progress":{"downloaded_bytes":0,"total_bytes":0}}"#).percent, None);
+    }
+
+    #[test]
+    fn parses_postprocess_lines() {
+        let (event, filename) = progress(r#"{"type":"postprocess","progress":{"status":"started","postprocessor":"ExtractAudio"}}"#);
+
+        assert_eq!(event.phase, ProgressPhase::PostProcessing);
+        assert_eq!(filename, None);
+    }
+
+    #[test]
+    fn parses_already_downloaded_notices() {
+        match parse_output_line("job", "[download] /music/Title (1).mp3 has already been downloaded") {
+            Some(OutputLine::AlreadyDownloaded(path)) => assert_eq!(path, "/music/Title (1).mp3"),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert!(parse_output_line("job", "[download] Destination: /music/Title.webm").is_none());
+    }
+
+    #[test]
+    fn parses_aria2c_summaries() {
+        let (event, filename) = progress("[#2089b0 1.2MiB/10MiB(12%) CN:16 DL:2.3MiB ETA:1m4s]");
+
+        assert_eq!(event.phase, ProgressPhase::Downloading);
+        assert_eq!(event.percent, Some(12.0));
+        assert_eq!(event.downloaded_bytes, Some((1.2 * MIB as f64) as u64));
+        assert_eq!(event.total_bytes, Some(10 * MIB));
+        assert_eq!(event.speed_bytes_per_sec, Some((2.3 * MIB as f64) as u64));
+        assert_eq!(event.eta_secs, Some(64));
+        assert_eq!(filename, None);
+
+        // Before the size is known aria2c prints no percentage
+        let event = parse("[#2089b0 0B/0B CN:1 DL:0B]");
+        assert_eq!(event.percent, None);
+        assert_eq!(event.downloaded_bytes, Some(0));
+        assert_eq!(event.eta_secs, None);
+    }
+
+    #[test]
+    fn ignores_plain_and_malformed_lines() {
+        for line in [
+            "",
+            "[youtube] dQw4w9WgXcQ: Downloading webpage",
+            "[download]  45.3% of ~10.50MiB at  2.31MiB/s ETA 00:03",
+            "{",
+            r#"{"type":"download","progress":{"downloaded_bytes":"NA"}}"#,
+            r#"{"type":"download"}"#,
+            r#"{"type":"unknown","progress":{}}"#,
+            r#"{"progress":{"downloaded_bytes":1}}"#,
+            "[#2089b0]",
+            "[#2089b0 1.2MiB(12%)]",
+        ] {
+            assert!(parse_output_line("job", line).is_none(), "parsed {:?}", line);
+        }
+    }
+
+    #[test]
+    fn parses_sizes_with_units() {
+        for (value, bytes) in [
+            ("512", Some(512)),
+            ("512B", Some(512)),
+            ("1.5KiB", Some(1536)),
+            ("10.50MiB", Some(11_010_048)),
+            ("2GiB", Some(2 * 1024 * MIB)),
+            ("1TiB", Some(1024 * 1024 * MIB)),
+            ("3kB", Some(3000)),
+            ("3KB", Some(3000)),
+            ("3MB", Some(3_000_000)),
+            ("1.5GB", Some(1_500_000_000)),
+            ("2TB", Some(2_000_000_000_000)),
+            ("", None),
+            ("MiB", None),
+            ("12XB", None),
+            ("1.2.3MiB", None),
+        ] {
+            assert_eq!(parse_size(value), bytes, "size {:?}", value);
+        }
+    }
+
+    #[test]
+    fn parses_aria2c_durations() {
+        assert_eq!(parse_duration_units("4s"), Some(4));
+        assert_eq!(parse_duration_units("1h2m3s"), Some(3723));
+        assert_eq!(parse_duration_units("5"), None);
+        assert_eq!(parse_duration_units("3x"), None);
+    }
+}
@@ -0,0 +1,327 @@
+//! Encrypted secret storage.
+//!
+//! Secrets are kept as a single JSON map encrypted with XChaCha20-Poly1305.
+//! The random 256-bit key is stored in its own file, readable only by the
+//! current user, so the encrypted file copied on its own reveals nothing.
+//! On Windows the key lives in the non-roaming local app data directory,
+//! apart from the data; on Linux and macOS both directories are the same,
+//! so anyone who can read the whole app directory can read the secrets.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use tauri::{AppHandle, Manager};
+
+use crate::config::AUTH_KEY_FILENAME;
+use crate::error::AppError;
+use crate::utils::paths::{app_data_path, set_aside};
+
+/// Length of the XChaCha20 nonce stored in front of the ciphertext.
+const NONCE_LEN: usize = 24;
+
+/// Length of the encryption key.
+const KEY_LEN: usize = 32;
+
+/// An encrypted file of named secrets.
+pub struct SecretStore {
+    path: PathBuf,
+    key_path: PathBuf,
+}
+
+impl SecretStore {
+    /// # Arguments
+    /// * `path` - Encrypted secrets file
+    /// * `key_path` - Key file, see [`create_key`]
+    pub fn new(path: PathBuf, key_path: PathBuf) -> Self {
+        Self { path, key_path }
+    }
+
+    /// Decrypt and return all secrets.
+    ///
+    /// # Returns
+    /// * `Ok(HashMap)` with the secrets; empty if nothing was saved yet
+    /// * `Err(AppError::SecretStoreFailed)` if the key is missing or the file cannot be decrypted
+    pub fn load(&self) -> Result<HashMap<String, String>, AppError> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let cipher = self.cipher()?;
+
+        if data.len() < NONCE_LEN {
+            return Err(AppError::SecretStoreFailed("Secrets file is corrupt.".to_string()));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let plaintext = cipher.decrypt(XNonce::from_slice(nonce), ciphertext).map_err(|_| {
+            AppError::SecretStoreFailed(
+                "Saved credentials could not be decrypted; they must be entered again.".to_string(),
+            )
+        })?;
+
+        serde_json::from_slice(&plaintext)
+            .map_err(|e| AppError::SecretStoreFailed(format!("Secrets file is corrupt: {}", e)))
+    }
+
+    /// Decrypt all secrets before changing them.
+    ///
+    /// A file that can no longer be decrypted is moved aside, so the next
+    /// save starts a new store instead of overwriting it, and the error is
+    /// returned with the backup's location.
+    pub fn load_for_update(&self) -> Result<HashMap<String, String>, AppError> {
+        match self.load() {
+            Err(AppError::SecretStoreFailed(message)) => {
+                let backup = set_aside(&self.path)?;
+                Err(AppError::SecretStoreFailed(format!(
+                    "{} The unreadable file was kept as {}.",
+                    message,
+                    backup.display()
+                )))
+            }
+            result => result,
+        }
+    }
+
+    /// Encrypt and write all secrets, replacing the previous file.
+    pub fn save(&self, secrets: &HashMap<String, String>) -> Result<(), AppError> {
+        let cipher = self.cipher()?;
+
+        let plaintext = serde_json::to_vec(secrets)
+            .map_err(|e| AppError::SecretStoreFailed(format!("Failed to serialize secrets: {}", e)))?;
+
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| AppError::SecretStoreFailed("Failed to encrypt secrets.".to_string()))?;
+
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+
+        let temp_path = self.path.with_extension("tmp");
+        let _ = fs::remove_file(&temp_path);
+        write_private(&temp_path, &data)?;
+        fs::rename(&temp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    /// Load the key.
+    fn cipher(&self) -> Result<XChaCha20Poly1305, AppError> {
+        match fs::read(&self.key_path) {
+            Ok(key) if key.len() == KEY_LEN => Ok(XChaCha20Poly1305::new(Key::from_slice(&key))),
+            Ok(_) => Err(AppError::SecretStoreFailed("Encryption key file is corrupt.".to_string())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(AppError::SecretStoreFailed(
+                "Encryption key is missing; saved credentials must be entered again.".to_string(),
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Create the encryption key file unless it already exists.
+///
+/// The key is written to a private temporary file and linked into place,
+/// so a concurrent creator never sees a partial key and an existing key is
+/// never replaced.
+pub fn create_key(key_path: &Path) -> Result<(), AppError> {
+    if key_path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = key_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = key_path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    write_private(&temp_path, &XChaCha20Poly1305::generate_key(&mut OsRng))?;
+    let linked = fs::hard_link(&temp_path, key_path);
+    let _ = fs::remove_file(&temp_path);
+
+    match linked {
+        Err(e) if e.kind() != ErrorKind::AlreadyExists => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Resolve the encryption key shared by the app's secret stores.
+fn app_key_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let key_dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| AppError::tauri(format!("Failed to resolve app local data directory: {}", e)))?;
+
+    Ok(key_dir.join(AUTH_KEY_FILENAME))
+}
+
+/// Create the shared encryption key on first run.
+///
+/// Called once at startup, before any secret store is used.
+pub fn init_app_secret_key(app: &AppHandle) -> Result<(), AppError> {
+    create_key(&app_key_path(app)?)
+}
+
+/// Open one of the app's secret stores.
+///
+/// The encrypted secrets live in the app data directory; the key lives in
+/// the app local data directory and is created by [`init_app_secret_key`].
+///
+/// # Arguments
+/// * `app` - App handle used to resolve the directories
+/// * `file_name` - Name of the encrypted secrets file
+///
+/// # Returns
+/// * `Ok(SecretStore)` on success
+/// * `Err(AppError)` if a directory cannot be resolved or created
+pub fn app_secret_store(app: &AppHandle, file_name: &str) -> Result<SecretStore, AppError> {
+    let path = app_data_path(app, file_name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(SecretStore::new(path, app_key_path(app)?))
+}
+
+/// Create a file that only the current user can read and write.
+///
+/// Fails if the file already exists, so existing permissions are never
+/// inherited. On Windows, files in the user's profile are private by default.
+///
+/// # Arguments
+/// * `path` - File to create
+/// * `data` - Contents to write
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(AppError)` if the file exists or cannot be written
+pub fn write_private(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+/// Create a directory that only the current user can access.
+///
+/// Fails if the directory already exists.
+pub fn create_private_dir(path: &Path) -> Result<(), AppError> {
+    let mut builder = fs::DirBuilder::new();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    builder.create(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("godspeed-secrets-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn store_in(dir: &Path, key_name: &str) -> SecretStore {
+        let key_path = dir.join(key_name);
+        create_key(&key_path).unwrap();
+        SecretStore::new(dir.join("secrets.bin"), key_path)
+    }
+
+    fn sample() -> HashMap<String, String> {
+        HashMap::from([("profile".to_string(), "hunter2-password".to_string())])
+    }
+
+    #[test]
+    fn round_trips_secrets() {
+        let dir = scratch_dir();
+        let store = store_in(&dir, "a.key");
+
+        assert!(store.load().unwrap().is_empty());
+
+        store.save(&sample()).unwrap();
+        assert_eq!(store.load().unwrap(), sample());
+
+        let data = fs::read(dir.join("secrets.bin")).unwrap();
+        assert!(!String::from_utf8_lossy(&data).contains("hunter2"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_tampered_ciphertext_and_wrong_keys() {
+        let dir = scratch_dir();
+        let store = store_in(&dir, "a.key");
+        store.save(&sample()).unwrap();
+
+        let other = store_in(&dir, "b.key");
+        assert!(matches!(other.load(), Err(AppError::SecretStoreFailed(_))));
+
+        let mut data = fs::read(dir.join("secrets.bin")).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        fs::write(dir.join("secrets.bin"), &data).unwrap();
+        assert!(matches!(store.load(), Err(AppError::SecretStoreFailed(_))));
+
+        fs::write(dir.join("secrets.bin"), b"short").unwrap();
+        assert!(matches!(store.load(), Err(AppError::SecretStoreFailed(_))));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_secrets_are_kept_as_a_backup() {
+        let dir = scratch_dir();
+        store_in(&dir, "a.key").save(&sample()).unwrap();
+        let original = fs::read(dir.join("secrets.bin")).unwrap();
+
+        let other = store_in(&dir, "b.key");
+        assert!(matches!(other.load_for_update(), Err(AppError::SecretStoreFailed(_))));
+        assert_eq!(fs::read(dir.join("secrets.bin.bad")).unwrap(), original);
+
+        // The next save starts a new store without touching the backup
+        assert!(other.load_for_update().unwrap().is_empty());
+        other.save(&sample()).unwrap();
+        assert_eq!(fs::read(dir.join("secrets.bin.bad")).unwrap(), original);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn key_is_created_once_and_never_replaced() {
+        let dir = scratch_dir();
+        let key_path = dir.join("nested").join("a.key");
+
+        let missing = SecretStore::new(dir.join("secrets.bin"), key_path.clone());
+        assert!(matches!(missing.save(&sample()), Err(AppError::SecretStoreFailed(_))));
+
+        create_key(&key_path).unwrap();
+        let key = fs::read(&key_path).unwrap();
+        assert_eq!(key.len(), KEY_LEN);
+
+        create_key(&key_path).unwrap();
+        assert_eq!(fs::read(&key_path).unwrap(), key);
+        assert_eq!(fs::read_dir(key_path.parent().unwrap()).unwrap().count(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,13 @@
+//! Quoting for argument strings that yt-dlp splits itself.
+//!
+//! yt-dlp parses `--postprocessor-args`, `--external-downloader-args` and
+//! `--config-locations` files with Python's POSIX `shlex`, so values put
+//! into them must be quoted the same way.
+
+/// Quote a value as a single POSIX shell word.
+///
+/// The value is wrapped in single quotes; embedded single quotes are closed,
+/// escaped and reopened, so no character keeps a special meaning.
+pub fn shlex_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r#"'"'"'"#))
+}
@@ -0,0 +1,370 @@
+//! Output filename template utilities.
+//!
+//! Validates user-supplied yt-dlp output templates such as
+//! `%(uploader)s/%(upload_date)s - %(title)s` and resolves them against
+//! probe metadata so the UI can preview the resulting path.
+
+use serde_json::Value;
+
+use crate::error::AppError;
+
+/// yt-dlp fields that may be used in a filename template.
+const ALLOWED_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "fulltitle",
+    "alt_title",
+    "display_id",
+    "ext",
+    "uploader",
+    "uploader_id",
+    "channel",
+    "channel_id",
+    "creator",
+    "timestamp",
+    "upload_date",
+    "release_date",
+    "release_year",
+    "duration",
+    "duration_string",
+    "view_count",
+    "extractor",
+    "extractor_key",
+    "webpage_url_domain",
+    "playlist",
+    "playlist_id",
+    "playlist_title",
+    "playlist_index",
+    "playlist_count",
+    "playlist_uploader",
+    "autonumber",
+    "artist",
+    "album",
+    "album_artist",
+    "track",
+    "track_number",
+    "disc_number",
+    "genre",
+    "series",
+    "season",
+    "season_number",
+    "episode",
+    "episode_number",
+    "format_id",
+    "resolution",
+    "width",
+    "height",
+    "fps",
+    "vcodec",
+    "acodec",
+];
+
+/// Printf conversion types accepted after a field.
+const CONVERSIONS: &str = "sdif";
+
+/// Printf flags and width/precision characters accepted before the conversion.
+const CONVERSION_FLAGS: &str = "#0-+ .0123456789";
+
+/// Placeholder yt-dlp uses for fields that are not available.
+const MISSING_VALUE: &str = "NA";
+
+/// Prefix added to the filename of numbered playlist entries.
+const NUMBERED_PREFIX: &str = "%(playlist_index)s - ";
+
+/// A parsed piece of an output template.
+enum Segment<'a> {
+    Literal(String),
+    Field {
+        name: &'a str,
+        default: Option<&'a str>,
+        flags: &'a str,
+        conversion: char,
+    },
+}
+
+/// Validate a filename template.
+///
+/// Rejects unknown fields, unsupported conversions, absolute paths and
+/// `..` components, so the output can never leave the output directory.
+///
+/// # Arguments
+/// * `template` - Template relative to the output directory, without extension
+///
+/// # Returns
+/// * `Ok(())` if the template is safe to pass to yt-dlp
+/// * `Err(AppError)` describing the first problem found
+pub fn validate_template(template: &str) -> Result<(), AppError> {
+    if template.trim().is_empty() {
+        return Err(AppError::logic("Filename template cannot be empty."));
+    }
+
+    if template.starts_with(['/', '\\']) {
+        return Err(AppError::logic("Filename template must be a relative path."));
+    }
+
+    let segments = parse_template(template)?;
+
+    for segment in &segments {
+        match segment {
+            Segment::Literal(text) => {
+                if text.chars().any(|c| c == ':' || c.is_control()) {
+                    return Err(AppError::logic(format!(
+                        "Filename template contains an invalid character: {}",
+                        text
+                    )));
+                }
+            }
+            Segment::Field { name, .. } => {
+                if !ALLOWED_FIELDS.contains(name) {
+                    return Err(AppError::logic(format!("Unknown template field: {}", name)));
+                }
+            }
+        }
+    }
+
+    // Only literal text can form path components; field values are sanitized by yt-dlp
+    let literal_only: String = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(text) => text.as_str(),
+            Segment::Field { .. } => "_",
+        })
+        .collect();
+
+    if literal_only
+        .split(['/', '\\'])
+        .any(|component| component.trim() == "..")
+    {
+        return Err(AppError::logic("Filename template cannot contain '..' components."));
+    }
+
+    Ok(())
+}
+
+/// Build the full yt-dlp filename template for a job.
+///
+/// Adds the playlist index prefix to the file name component when
+/// `numbered` is set, and appends `.%(ext)s` unless already present.
+pub fn build_output_template(template: &str, numbered: bool) -> String {
+    let mut output = if numbered {
+        match template.rfind(['/', '\\']) {
+            Some(split) => format!(
+                "{}{}{}",
+                &template[..=split],
+                NUMBERED_PREFIX,
+                &template[split + 1..]
+            ),
+            None => format!("{}{}", NUMBERED_PREFIX, template),
+        }
+    } else {
+        template.to_string()
+    };
+
+    if !output.ends_with("%(ext)s") {
+        output.push_str(".%(ext)s");
+    }
+
+    output
+}
+
+/// Append a literal suffix to the file name of a template, before any `.%(ext)s`.
+pub fn with_suffix(template: &str, suffix: &str) -> String {
+    match template.strip_suffix(".%(ext)s") {
+        Some(stem) => format!("{}{}.%(ext)s", stem, suffix),
+        None => format!("{}{}", template, suffix),
+    }
+}
+
+/// Resolve a template against yt-dlp `-J` metadata.
+///
+/// Mirrors yt-dlp's behaviour closely enough for a preview: missing fields
+/// become `NA` (or the `|default`), and path-unsafe characters in values are
+/// replaced like `--windows-filenames` does.
+pub fn resolve_template(template: &str, info: &Value) -> Result<String, AppError> {
+    let mut resolved = String::new();
+
+    for segment in parse_template(template)? {
+        match segment {
+            Segment::Literal(text) => resolved.push_str(&text),
+            Segment::Field {
+                name,
+                default,
+                flags,
+                conversion,
+            } => {
+                let value = match info.get(name) {
+                    Some(Value::Null) | None => default.unwrap_or(MISSING_VALUE).to_string(),
+                    Some(value) => format_value(value, flags, conversion),
+                };
+                resolved.push_str(&sanitize_value(&value));
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Split a template into literal text and `%(field)X` placeholders.
+fn parse_template(template: &str) -> Result<Vec<Segment<'_>>, AppError> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(position) = rest.find('%') {
+        literal.push_str(&rest[..position]);
+        rest = &rest[position + 1..];
+
+        if let Some(after) = rest.strip_prefix('%') {
+            literal.push('%');
+            rest = after;
+            continue;
+        }
+
+        let Some(after) = rest.strip_prefix('(') else {
+            return Err(AppError::logic(
+                "Stray '%' in filename template (use '%%' for a literal percent sign).",
+            ));
+        };
+        let Some((key, after)) = after.split_once(')') else {
+            return Err(AppError::logic("Unclosed field in filename template."));
+        };
+
+        let flags_len = after
+            .find(|c: char| !CONVERSION_FLAGS.contains(c))
+            .unwrap_or(after.len());
+        let (flags, after) = after.split_at(flags_len);
+
+        let conversion = after
+            .chars()
+            .next()
+            .filter(|c| CONVERSIONS.contains(*c))
+            .ok_or_else(|| {
+                AppError::logic(format!(
+                    "Missing or unsupported conversion after field '{}' (expected one of: {}).",
+                    key, CONVERSIONS
+                ))
+            })?;
+        rest = &after[conversion.len_utf8()..];
+
+        let (name, default) = match key.split_once('|') {
+            Some((name, default)) => (name, Some(default)),
+            None => (key, None),
+        };
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(std::mem::take(&mut literal)));
+        }
+        segments.push(Segment::Field {
+            name: name.trim(),
+            default,
+            flags,
+            conversion,
+        });
+    }
+
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// Format a metadata value with a printf-style conversion.
+fn format_value(value: &Value, flags: &str, conversion: char) -> String {
+    let spec = flags.trim_start_matches(['#', '-', '+', ' ']);
+    let zero_pad = spec.starts_with('0');
+    let width: usize = spec
+        .trim_start_matches('0')
+        .split('.')
+        .next()
+        .and_then(|w| w.parse().ok())
+        .unwrap_or(0);
+
+    let text = match (conversion, value) {
+        ('d' | 'i', Value::Number(n)) => n
+            .as_i64()
+            .or_else(|| n.as_f64().map(|f| f as i64))
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| n.to_string()),
+        (_, Value::String(s)) => s.clone(),
+        (_, other) => other.to_string(),
+    };
+
+    if zero_pad {
+        format!("{:0>width$}", text, width = width)
+    } else {
+        format!("{:>width$}", text, width = width)
+    }
+}
+
+/// Replace characters that are not allowed in file names.
+fn sanitize_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '/' => '⧸',
+            '\\' => '⧹',
+            ':' => '：',
+            '*' => '＊',
+            '?' => '？',
+            '"' => '＂',
+            '<' => '＜',
+            '>' => '＞',
+            '|' => '｜',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_relative_templates() {
+        assert!(validate_template("%(title)s").is_ok());
+        assert!(validate_template("%(uploader)s/%(upload_date)s - %(title)s").is_ok());
+        assert!(validate_template("Music/%(artist|Unknown)s/%(track_number)02d %(title)s").is_ok());
+        assert!(validate_template("Version 1..2/%(title)s").is_ok());
+    }
+
+    #[test]
+    fn rejects_parent_components() {
+        for template in [
+            "../%(title)s",
+            "..",
+            "a/../%(title)s",
+            "%(uploader)s/../../%(title)s",
+            "%(title)s/..",
+            r"..\%(title)s",
+            r"a\..\%(title)s",
+            " .. /%(title)s",
+        ] {
+            assert!(validate_template(template).is_err(), "accepted {:?}", template);
+        }
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        for template in [
+            "/%(title)s",
+            "/tmp/%(title)s",
+            r"\%(title)s",
+            r"\\server\share\%(title)s",
+            r"C:\Users\%(title)s",
+            "C:/%(title)s",
+        ] {
+            assert!(validate_template(template).is_err(), "accepted {:?}", template);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_empty_templates() {
+        assert!(validate_template("").is_err());
+        assert!(validate_template("   ").is_err());
+        assert!(validate_template("%(filepath)s").is_err());
+        assert!(validate_template("100%").is_err());
+    }
+}
@@ -120,11 +120,9 @@ pub fn copy_with_retry(source: &Path, target: &Path, max_retries: u32) -> Result
 
                 // If it's a sharing violation, wait and retry
                 if let Some(ref err) = last_error {
-                    if err.raw_os_error() == Some(32) || err.raw_os_error() == Some(33) {
-                        if attempt < max_retries - 1 {
-                            std::thread::sleep(std::time::Duration::from_millis(500));
-                            continue;
-                        }
+                    if (err.raw_os_error() == Some(32) || err.raw_os_error() == Some(33)) && attempt < max_retries - 1 {
+                        std::thread::sleep(std::time::Duration::from_millis(500));
+                        continue;
                     }
                 }
 
@@ -138,3 +136,25 @@ pub fn copy_with_retry(source: &Path, target: &Path, max_retries: u32) -> Result
         .map(AppError::Io)
         .unwrap_or_else(|| AppError::logic("Copy failed with unknown error")))
 }
+
+/// Set the execute bit on a downloaded binary.
+///
+/// A no-op on Windows, where executability follows the file extension.
+///
+/// # Arguments
+/// * `path` - File to mark executable
+///
+/// # Returns
+/// * `Ok(())` on success
+/// * `Err(AppError)` if the permissions cannot be changed
+#[cfg(unix)]
+pub fn make_executable(path: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn make_executable(_path: &Path) -> Result<(), AppError> {
+    Ok(())
+}
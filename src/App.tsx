@@ -20,12 +20,78 @@ import QualitySelector from "./components/QualitySelector";
 import ProgressBar from "./components/ProgressBar";
 import TerminalDrawer from "./components/TerminalDrawer";
 import SuccessModal from "./components/SuccessModal";
-import SettingsView from "./components/SettingsView";
+import SettingsView, { NetworkConfig, UpdateSources } from "./components/SettingsView";
 
 const MAX_LOGS = 200;
 
 type ViewType = "home" | "settings";
 
+/** Raw yt-dlp output line (mirrors `LogEvent` in Rust). */
+interface LogEvent {
+  job_id: string;
+  line: string;
+}
+
+/** Structured download progress (mirrors `ProgressEvent` in Rust). */
+interface ProgressEvent {
+  job_id: string;
+  phase: "extracting" | "downloading" | "post_processing" | "finished";
+  percent: number | null;
+  downloaded_bytes: number | null;
+  total_bytes: number | null;
+  speed_bytes_per_sec: number | null;
+  eta_secs: number | null;
+  fragment_index: number | null;
+  fragment_count: number | null;
+}
+
+/** Error returned by Rust commands (mirrors `AppError` serialization). */
+interface AppError {
+  code: string;
+  message: string;
+}
+
+/**
+ * Render a command error for the terminal, e.g. "GEO_BLOCKED: ...".
+ */
+function describeError(error: unknown): string {
+  if (typeof error === "object" && error !== null && "code" in error && "message" in error) {
+    const { code, message } = error as AppError;
+    return `${code}: ${message}`;
+  }
+  return String(error);
+}
+
+/** Result of `download_video` (mirrors `DownloadOutcome` in Rust). */
+type DownloadOutcome =
+  | { status: "completed"; file_path: string | null }
+  | { status: "skipped"; reason: "in_archive" | "file_exists"; file_path: string | null }
+  | { status: "paused" }
+  | { status: "cancelled" };
+
+/** Persisted user settings (mirrors `SettingsResponse` in Rust). */
+interface Settings {
+  version: number;
+  output_path: string;
+  audio_format: { format: string; quality?: { cbr?: number; vbr?: number } };
+  filename_template: string;
+  auto_clear_url: boolean;
+  terminal_enabled: boolean;
+  update_sources: UpdateSources;
+  yt_dlp_channel: "stable" | "nightly";
+  beta_updates: boolean;
+  allowed_sites: string[];
+  network: NetworkConfig;
+  /** Settings load problems and rejected environment overrides, reported by the backend; never saved. */
+  warnings: string[];
+}
+
+/** Download completion notice (mirrors `CompleteEvent` in Rust). */
+interface CompleteEvent {
+  job_id: string;
+  file_path: string | null;
+}
+
 /**
  * Main application component with cyberpunk-industrial dashboard layout.
  * Implements Smart Download Lifecycle: Idle → Downloading → Success → Reset.
@@ -55,6 +121,9 @@ function App() {
   const [downloadedFilename, setDownloadedFilename] = useState("");
   const [downloadedFilePath, setDownloadedFilePath] = useState("");
 
+  // Persisted settings (loaded from Rust)
+  const [settings, setSettings] = useState<Settings | null>(null);
+
   // App version (fetched from Tauri)
   const [appVersion, setAppVersion] = useState("...");
 
@@ -65,6 +134,13 @@ function App() {
     getVersion().then(setAppVersion).catch(() => setAppVersion("?.?.?"));
   }, []);
 
+  // Load persisted settings on mount
+  useEffect(() => {
+    invoke<Settings>("get_settings")
+      .then(applySettings)
+      .catch((error) => console.error("Failed to load settings:", error));
+  }, []);
+
   // When terminal is disabled, also hide it
   useEffect(() => {
     if (!isTerminalEnabled) {
@@ -74,16 +150,17 @@ function App() {
 
   // Listen for download events from Rust
   useEffect(() => {
+    let unlistenLog: UnlistenFn | undefined;
     let unlistenProgress: UnlistenFn | undefined;
     let unlistenComplete: UnlistenFn | undefined;
     isMountedRef.current = true;
 
     const setupListeners = async () => {
-      // Listen for progress events
-      unlistenProgress = await listen<string>("download-progress", (event) => {
+      // Listen for raw output lines
+      unlistenLog = await listen<LogEvent>("download-log", (event) => {
         if (!isMountedRef.current) return;
 
-        const line = event.payload;
+        const line = event.payload.line;
 
         // Limit logs to prevent memory overflow
         setLogs((prev) => [...prev.slice(-MAX_LOGS + 1), line]);
@@ -94,16 +171,20 @@ function App() {
             setShowTerminal(true);
           }
         }
+      });
 
-        // Parse percentage from yt-dlp output: [download]  XX.X%
-        const match = line.match(/\[download\]\s+(\d+\.?\d*)%/);
-        if (match) {
-          const percent = parseFloat(match[1]);
+      // Listen for structured progress events
+      unlistenProgress = await listen<ProgressEvent>("download-progress", (event) => {
+        if (!isMountedRef.current) return;
+
+        const { phase, percent } = event.payload;
+
+        if (percent !== null) {
           setProgress(Math.min(percent, 100));
         }
 
-        // Check for completion - show success modal
-        if (line.includes("completed") || line.includes("finished")) {
+        // Completion - show success modal
+        if (phase === "finished") {
           setIsDownloading(false);
           setIsComplete(true);
           setProgress(100);
@@ -112,10 +193,10 @@ function App() {
       });
 
       // Listen for download complete event with file path
-      unlistenComplete = await listen<string>("download-complete", (event) => {
+      unlistenComplete = await listen<CompleteEvent>("download-complete", (event) => {
         if (!isMountedRef.current) return;
 
-        const filePath = event.payload;
+        const filePath = event.payload.file_path ?? "";
         setDownloadedFilePath(filePath);
 
         // Extract just the filename from the full path
@@ -128,11 +209,44 @@ function App() {
 
     return () => {
       isMountedRef.current = false;
+      if (unlistenLog) unlistenLog();
       if (unlistenProgress) unlistenProgress();
       if (unlistenComplete) unlistenComplete();
     };
   }, [isTerminalEnabled]);
 
+  /**
+   * Mirror persisted settings into UI state.
+   */
+  const applySettings = (value: Settings) => {
+    setSettings(value);
+    setOutputPath(value.output_path);
+    setAutoClearUrl(value.auto_clear_url);
+    setIsTerminalEnabled(value.terminal_enabled);
+
+    const cbr = value.audio_format.format === "mp3" ? value.audio_format.quality?.cbr : undefined;
+    if (cbr) {
+      setAudioQuality(`${cbr}k`);
+    }
+  };
+
+  /**
+   * Validate and save a settings change in Rust.
+   * The UI only changes once the backend accepted the new value.
+   */
+  const saveSettings = async (patch: Partial<Settings>) => {
+    if (!settings) return;
+
+    try {
+      const saved = await invoke<Settings>("update_settings", {
+        settings: { ...settings, ...patch },
+      });
+      applySettings(saved);
+    } catch (error) {
+      setLogs((prev) => [...prev.slice(-MAX_LOGS + 1), `[ERROR] ${describeError(error)}`]);
+    }
+  };
+
   /**
    * Open folder selection dialog.
    */
@@ -144,7 +258,7 @@ function App() {
         title: "Select Download Folder",
       });
       if (selected) {
-        setOutputPath(selected as string);
+        await saveSettings({ output_path: selected as string });
       }
     } catch (error) {
       console.error("Failed to select folder:", error);
@@ -210,14 +324,25 @@ function App() {
     ]);
 
     try {
-      await invoke("download_video", {
-        url: url.trim(),
-        outputPath: outputPath,
-        quality: audioQuality,
+      const outcome = await invoke<DownloadOutcome>("download_video", {
+        request: {
+          url: url.trim(),
+          output_path: outputPath,
+          mode: {
+            mode: "audio",
+            format: { format: "mp3", quality: { cbr: parseInt(audioQuality, 10) } },
+          },
+        },
       });
+
+      // Skipped downloads never reach the "finished" phase
+      if (outcome.status === "skipped" && isMountedRef.current) {
+        setIsDownloading(false);
+        setProgress(0);
+      }
     } catch (error) {
       if (isMountedRef.current) {
-        setLogs((prev) => [...prev.slice(-MAX_LOGS + 1), `[ERROR] ${error}`]);
+        setLogs((prev) => [...prev.slice(-MAX_LOGS + 1), `[ERROR] ${describeError(error)}`]);
         setIsDownloading(false);
         if (isTerminalEnabled) {
           setShowTerminal(true);
@@ -349,7 +474,11 @@ function App() {
                     {/* Quality Selector */}
                     <QualitySelector
                       quality={audioQuality}
-                      onChange={setAudioQuality}
+                      onChange={(quality) =>
+                        saveSettings({
+                          audio_format: { format: "mp3", quality: { cbr: parseInt(quality, 10) } },
+                        })
+                      }
                       disabled={isDownloading}
                     />
 
@@ -411,9 +540,23 @@ function App() {
               >
                 <SettingsView
                   isTerminalEnabled={isTerminalEnabled}
-                  onToggleTerminalEnabled={setIsTerminalEnabled}
+                  onToggleTerminalEnabled={(value) => saveSettings({ terminal_enabled: value })}
                   autoClearUrl={autoClearUrl}
-                  onToggleAutoClear={setAutoClearUrl}
+                  onToggleAutoClear={(value) => saveSettings({ auto_clear_url: value })}
+                  updateSources={settings?.update_sources ?? null}
+                  onSaveUpdateSources={(sources) => saveSettings({ update_sources: sources })}
+                  settingsWarnings={settings?.warnings ?? []}
+                  ytDlpNightly={settings?.yt_dlp_channel === "nightly"}
+                  onToggleYtDlpNightly={(value) => saveSettings({ yt_dlp_channel: value ? "nightly" : "stable" })}
+                  betaUpdates={settings?.beta_updates ?? false}
+                  onToggleBetaUpdates={(value) => saveSettings({ beta_updates: value })}
+                  allowedSites={settings?.allowed_sites ?? null}
+                  onSaveAllowedSites={(sites) => saveSettings({ allowed_sites: sites })}
+                  filenameTemplate={settings?.filename_template ?? null}
+                  onSaveFilenameTemplate={(template) => saveSettings({ filename_template: template })}
+                  outputPath={outputPath}
+                  network={settings?.network ?? null}
+                  onSaveNetwork={(network) => saveSettings({ network })}
                   appVersion={appVersion}
                 />
               </div>
@@ -0,0 +1,274 @@
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { KeyRound, Trash2, Plus } from "lucide-react";
+
+/** How a profile authenticates (mirrors `AuthMethod` in Rust). */
+type AuthMethod =
+    | { kind: "cookies_file" }
+    | { kind: "browser"; browser: string; profile: string | null }
+    | { kind: "password"; username: string }
+    | { kind: "netrc"; location: string | null };
+
+/** Credentials applied to downloads from a set of sites (mirrors `AuthProfile` in Rust). */
+interface AuthProfile {
+    id: string;
+    name: string;
+    sites: string[];
+    method: AuthMethod;
+}
+
+const BROWSERS = ["brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale"];
+
+const METHOD_LABELS: Record<AuthMethod["kind"], string> = {
+    cookies_file: "Cookies file",
+    browser: "Browser cookies",
+    password: "Username & password",
+    netrc: ".netrc file",
+};
+
+function describeError(error: unknown): string {
+    if (typeof error === "object" && error !== null && "message" in error) {
+        return String((error as { message: unknown }).message);
+    }
+    return String(error);
+}
+
+function emptyMethod(kind: AuthMethod["kind"]): AuthMethod {
+    switch (kind) {
+        case "cookies_file":
+            return { kind };
+        case "browser":
+            return { kind, browser: "chrome", profile: null };
+        case "password":
+            return { kind, username: "" };
+        case "netrc":
+            return { kind, location: null };
+    }
+}
+
+/**
+ * Per-site authentication profiles for members-only, age-gated and private content.
+ * Passwords and cookies are sent to the backend once and stored encrypted; they are never read back.
+ */
+function AuthProfiles() {
+    const [profiles, setProfiles] = useState<AuthProfile[]>([]);
+    const [draft, setDraft] = useState<AuthProfile | null>(null);
+    const [sitesText, setSitesText] = useState("");
+    const [password, setPassword] = useState("");
+    const [cookiesFile, setCookiesFile] = useState<string | null>(null);
+    const [error, setError] = useState("");
+
+    useEffect(() => {
+        invoke<AuthProfile[]>("list_auth_profiles")
+            .then(setProfiles)
+            .catch((e) => setError(describeError(e)));
+    }, []);
+
+    const startEdit = (profile: AuthProfile | null) => {
+        const next = profile ?? { id: "", name: "", sites: [], method: emptyMethod("cookies_file") };
+        setDraft(next);
+        setSitesText(next.sites.join(", "));
+        setPassword("");
+        setCookiesFile(null);
+        setError("");
+    };
+
+    const pickCookiesFile = async () => {
+        const selected = await open({
+            multiple: false,
+            title: "Select Cookies File",
+            filters: [{ name: "Netscape cookies", extensions: ["txt"] }],
+        });
+        if (selected) {
+            setCookiesFile(selected as string);
+        }
+    };
+
+    const save = async () => {
+        if (!draft) return;
+
+        const profile = {
+            ...draft,
+            sites: sitesText.split(/[\s,]+/).filter((site) => site.length > 0),
+        };
+
+        try {
+            const saved = await invoke<AuthProfile>("save_auth_profile", {
+                profile,
+                password: password || null,
+                cookiesFile,
+            });
+            setProfiles((prev) =>
+                prev.some((p) => p.id === saved.id) ? prev.map((p) => (p.id === saved.id ? saved : p)) : [...prev, saved]
+            );
+            setDraft(null);
+            setPassword("");
+            setError("");
+        } catch (e) {
+            setError(describeError(e));
+        }
+    };
+
+    const remove = async (id: string) => {
+        try {
+            await invoke("delete_auth_profile", { id });
+            setProfiles((prev) => prev.filter((p) => p.id !== id));
+        } catch (e) {
+            setError(describeError(e));
+        }
+    };
+
+    const inputClass =
+        "w-full bg-black/40 border border-white/10 rounded-lg px-3 py-2 text-sm text-white font-mono placeholder-white/30 focus:outline-none focus:border-[#00ff88]/50 transition-all";
+
+    return (
+        <div className="space-y-4">
+            <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4">
+                Auth Profiles
+            </h2>
+
+            {profiles.map((profile) => (
+                <div key={profile.id} className="flex items-center justify-between gap-4 py-2">
+                    <button onClick={() => startEdit(profile)} className="flex items-start gap-4 min-w-0 text-left">
+                        <KeyRound className="w-5 h-5 mt-0.5 text-white/40" />
+                        <div className="min-w-0">
+                            <h3 className="text-white font-medium">{profile.name}</h3>
+                            <p className="text-sm text-white/40 truncate">
+                                {METHOD_LABELS[profile.method.kind]} · {profile.sites.join(", ")}
+                            </p>
+                        </div>
+                    </button>
+                    <button
+                        onClick={() => remove(profile.id)}
+                        className="text-white/40 hover:text-amber-400 transition-colors"
+                        title="Delete profile"
+                    >
+                        <Trash2 className="w-4 h-4" />
+                    </button>
+                </div>
+            ))}
+
+            {draft ? (
+                <div className="space-y-3">
+                    <input
+                        type="text"
+                        placeholder="Profile name"
+                        value={draft.name}
+                        onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+                        className={inputClass}
+                    />
+                    <input
+                        type="text"
+                        placeholder="Sites, e.g. youtube.com, patreon.com"
+                        value={sitesText}
+                        onChange={(e) => setSitesText(e.target.value)}
+                        className={inputClass}
+                    />
+                    <select
+                        value={draft.method.kind}
+                        onChange={(e) => setDraft({ ...draft, method: emptyMethod(e.target.value as AuthMethod["kind"]) })}
+                        className={inputClass}
+                    >
+                        {Object.entries(METHOD_LABELS).map(([kind, label]) => (
+                            <option key={kind} value={kind}>
+                                {label}
+                            </option>
+                        ))}
+                    </select>
+
+                    {draft.method.kind === "cookies_file" && (
+                        <button onClick={pickCookiesFile} className={`${inputClass} text-left text-white/60`}>
+                            {cookiesFile ?? (draft.id ? "Keep imported cookies (click to replace)" : "Select cookies.txt...")}
+                        </button>
+                    )}
+
+                    {draft.method.kind === "browser" && (
+                        <>
+                            <select
+                                value={draft.method.browser}
+                                onChange={(e) =>
+                                    setDraft({ ...draft, method: { ...draft.method, browser: e.target.value } as AuthMethod })
+                                }
+                                className={inputClass}
+                            >
+                                {BROWSERS.map((browser) => (
+                                    <option key={browser} value={browser}>
+                                        {browser}
+                                    </option>
+                                ))}
+                            </select>
+                            <input
+                                type="text"
+                                placeholder="Browser profile (optional)"
+                                value={draft.method.profile ?? ""}
+                                onChange={(e) =>
+                                    setDraft({ ...draft, method: { ...draft.method, profile: e.target.value || null } as AuthMethod })
+                                }
+                                className={inputClass}
+                            />
+                        </>
+                    )}
+
+                    {draft.method.kind === "password" && (
+                        <>
+                            <input
+                                type="text"
+                                placeholder="Username"
+                                value={draft.method.username}
+                                onChange={(e) =>
+                                    setDraft({ ...draft, method: { kind: "password", username: e.target.value } })
+                                }
+                                className={inputClass}
+                            />
+                            <input
+                                type="password"
+                                placeholder={draft.id ? "Password (leave empty to keep)" : "Password"}
+                                value={password}
+                                onChange={(e) => setPassword(e.target.value)}
+                                className={inputClass}
+                            />
+                        </>
+                    )}
+
+                    {draft.method.kind === "netrc" && (
+                        <input
+                            type="text"
+                            placeholder=".netrc location (optional, defaults to ~/.netrc)"
+                            value={draft.method.location ?? ""}
+                            onChange={(e) => setDraft({ ...draft, method: { kind: "netrc", location: e.target.value || null } })}
+                            className={inputClass}
+                        />
+                    )}
+
+                    <div className="flex gap-3">
+                        <button
+                            onClick={save}
+                            className="px-4 py-2 rounded-lg bg-[#00ff88]/10 text-[#00ff88] text-sm font-medium hover:bg-[#00ff88]/20 transition-colors"
+                        >
+                            Save Profile
+                        </button>
+                        <button
+                            onClick={() => setDraft(null)}
+                            className="px-4 py-2 rounded-lg text-white/40 text-sm hover:text-white/70 transition-colors"
+                        >
+                            Cancel
+                        </button>
+                    </div>
+                </div>
+            ) : (
+                <button
+                    onClick={() => startEdit(null)}
+                    className="flex items-center gap-2 text-sm text-white/40 hover:text-white/70 transition-colors"
+                >
+                    <Plus className="w-4 h-4" />
+                    Add auth profile
+                </button>
+            )}
+
+            {error && <p className="text-sm text-amber-400/80">{error}</p>}
+        </div>
+    );
+}
+
+export default AuthProfiles;
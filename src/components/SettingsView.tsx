@@ -1,5 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import AuthProfiles from "./AuthProfiles";
 
 import {
     Terminal,
@@ -12,14 +14,12 @@ import {
     Download,
     Sparkles,
     RefreshCw,
+    RotateCcw,
     Shield,
 } from "lucide-react";
 
 // Note: APP_VERSION is now passed as a prop from App.tsx
 
-// Placeholder URL for engine update - replace with actual GitHub release asset URL
-const ENGINE_UPDATE_URL = "https://github.com/ThanathonTH/godspeed-downloader/releases/download/v2.1.0/engine_v12.zip";
-
 // Types
 type UpdateStatus = "idle" | "loading" | "success" | "error";
 type AppUpdateStatus = "idle" | "checking" | "up-to-date" | "update-available" | "error";
@@ -27,8 +27,53 @@ type InstallStatus = "idle" | "downloading" | "installing" | "error";
 
 interface UpdateInfo {
     update_available: boolean;
-    latest_version: string;
+    /** Newest release on the selected channel, even if not newer than this version. */
+    latest_version: string | null;
+    /** Version offered for install; null without an update. */
+    update_version: string | null;
     download_url: string;
+    package: "app_image" | "deb" | "rpm" | "dmg" | "msi" | "exe" | null;
+    prerelease: boolean;
+    release_notes: string | null;
+    published_at: string | null;
+}
+
+/** Byte-level update download progress (mirrors `DownloadProgressEvent` in Rust). */
+interface DownloadProgressEvent {
+    download_id: string;
+    downloaded_bytes: number;
+    total_bytes: number | null;
+}
+
+/**
+ * Format download progress as "12.3 / 85.0 MB (14%)"
+ */
+function formatDownloadProgress(progress: DownloadProgressEvent | null): string {
+    if (!progress) return "";
+
+    const toMb = (bytes: number) => (bytes / (1024 * 1024)).toFixed(1);
+    if (!progress.total_bytes) {
+        return `Downloaded ${toMb(progress.downloaded_bytes)} MB`;
+    }
+
+    const percent = Math.floor((progress.downloaded_bytes / progress.total_bytes) * 100);
+    return `Downloaded ${toMb(progress.downloaded_bytes)} / ${toMb(progress.total_bytes)} MB (${percent}%)`;
+}
+
+/** Update endpoints (mirrors `UpdateSources` in Rust). */
+export interface UpdateSources {
+    app_feed: "github_releases" | "manifest";
+    app_url: string;
+    engine_package_url: string;
+    engine_index_url: string;
+}
+
+/** Proxy and bandwidth settings (mirrors `NetworkConfig` in Rust). Empty strings mean "not set". */
+export interface NetworkConfig {
+    proxy: string;
+    limit_rate: string;
+    source_address: string;
+    ca_bundle: string;
 }
 
 interface SettingsViewProps {
@@ -37,6 +82,21 @@ interface SettingsViewProps {
     autoClearUrl: boolean;
     onToggleAutoClear: (value: boolean) => void;
     appVersion: string;
+    updateSources: UpdateSources | null;
+    onSaveUpdateSources: (sources: UpdateSources) => void;
+    /** Problems found when the settings were loaded, e.g. rejected overrides. */
+    settingsWarnings: string[];
+    ytDlpNightly: boolean;
+    onToggleYtDlpNightly: (value: boolean) => void;
+    betaUpdates: boolean;
+    onToggleBetaUpdates: (value: boolean) => void;
+    allowedSites: string[] | null;
+    onSaveAllowedSites: (sites: string[]) => void;
+    filenameTemplate: string | null;
+    onSaveFilenameTemplate: (template: string) => void;
+    outputPath: string;
+    network: NetworkConfig | null;
+    onSaveNetwork: (network: NetworkConfig) => void;
 }
 
 /**
@@ -87,6 +147,20 @@ function SettingsView({
     autoClearUrl,
     onToggleAutoClear,
     appVersion,
+    updateSources,
+    onSaveUpdateSources,
+    settingsWarnings,
+    ytDlpNightly,
+    onToggleYtDlpNightly,
+    betaUpdates,
+    onToggleBetaUpdates,
+    allowedSites,
+    onSaveAllowedSites,
+    filenameTemplate,
+    onSaveFilenameTemplate,
+    outputPath,
+    network,
+    onSaveNetwork,
 }: SettingsViewProps) {
     // App update state
     const [appUpdateStatus, setAppUpdateStatus] = useState<AppUpdateStatus>("idle");
@@ -98,14 +172,49 @@ function SettingsView({
     // Engine update state
     const [engineUpdateStatus, setEngineUpdateStatus] = useState<UpdateStatus>("idle");
     const [engineUpdateMessage, setEngineUpdateMessage] = useState("");
+    const [engineBinariesStatus, setEngineBinariesStatus] = useState<UpdateStatus>("idle");
+    const [engineBinariesMessage, setEngineBinariesMessage] = useState("");
+    const [engineRollbackStatus, setEngineRollbackStatus] = useState<UpdateStatus>("idle");
+    const [engineRollbackMessage, setEngineRollbackMessage] = useState("");
+    const [engineUpdatesEnabled, setEngineUpdatesEnabled] = useState(true);
+
+    // Update download progress, keyed by area
+    const [engineProgress, setEngineProgress] = useState<DownloadProgressEvent | null>(null);
+    const [appUpdateProgress, setAppUpdateProgress] = useState<DownloadProgressEvent | null>(null);
 
     /**
-     * Check for app updates on mount
+     * Track byte-level progress of engine and app update downloads
      */
     useEffect(() => {
-        checkForUpdates();
+        const unlisten = listen<DownloadProgressEvent>("http-download-progress", (event) => {
+            if (event.payload.download_id === "app-update") {
+                setAppUpdateProgress(event.payload);
+            } else if (event.payload.download_id.startsWith("engine")) {
+                setEngineProgress(event.payload);
+            }
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, []);
+
+    /**
+     * Engine updates need a signing key embedded in the build
+     */
+    useEffect(() => {
+        invoke<boolean>("engine_updates_enabled")
+            .then(setEngineUpdatesEnabled)
+            .catch(() => setEngineUpdatesEnabled(false));
     }, []);
 
+    /**
+     * Check for app updates on mount and when the update channel changes
+     */
+    useEffect(() => {
+        checkForUpdates();
+    }, [betaUpdates]);
+
     /**
      * Check for app updates via GitHub Releases API
      */
@@ -144,6 +253,7 @@ function SettingsView({
 
         setInstallStatus("downloading");
         setInstallError("");
+        setAppUpdateProgress(null);
 
         try {
             await invoke("install_app_update", {
@@ -163,11 +273,10 @@ function SettingsView({
     const handleEngineUpdate = async () => {
         setEngineUpdateStatus("loading");
         setEngineUpdateMessage("");
+        setEngineProgress(null);
 
         try {
-            const result = await invoke<string>("install_engine_update", {
-                url: ENGINE_UPDATE_URL,
-            });
+            const result = await invoke<string>("install_engine_update");
             setEngineUpdateStatus("success");
             setEngineUpdateMessage(result);
 
@@ -182,6 +291,57 @@ function SettingsView({
         }
     };
 
+    /**
+     * Update only the engine binaries that have a newer build
+     */
+    const handleEngineBinariesUpdate = async () => {
+        setEngineBinariesStatus("loading");
+        setEngineBinariesMessage("");
+        setEngineProgress(null);
+
+        try {
+            const result = await invoke<string>("update_engine");
+            setEngineBinariesStatus("success");
+            setEngineBinariesMessage(result);
+
+            // Auto-reset success state after 5 seconds
+            setTimeout(() => {
+                setEngineBinariesStatus("idle");
+                setEngineBinariesMessage("");
+            }, 5000);
+        } catch (error) {
+            setEngineBinariesStatus("error");
+            setEngineBinariesMessage(formatErrorMessage(String(error)));
+        }
+    };
+
+    /**
+     * Restore the engine replaced by the last update
+     */
+    const handleEngineRollback = async () => {
+        setEngineRollbackStatus("loading");
+        setEngineRollbackMessage("");
+
+        try {
+            const result = await invoke<string>("rollback_engine");
+            setEngineRollbackStatus("success");
+            setEngineRollbackMessage(result);
+
+            // Auto-reset success state after 5 seconds
+            setTimeout(() => {
+                setEngineRollbackStatus("idle");
+                setEngineRollbackMessage("");
+            }, 5000);
+        } catch (error) {
+            setEngineRollbackStatus("error");
+            setEngineRollbackMessage(formatErrorMessage(String(error)));
+        }
+    };
+
+    const engineUpdatesDisabledReason = engineUpdatesEnabled
+        ? undefined
+        : "Engine updates are unavailable in this build (no engine signing key)";
+
     return (
         <div className="flex-1 flex flex-col p-8 overflow-y-auto">
             {/* Header */}
@@ -199,6 +359,16 @@ function SettingsView({
 
             {/* Settings Panel */}
             <div className="glass-panel p-6 max-w-2xl space-y-6">
+                {settingsWarnings.length > 0 && (
+                    <div className="space-y-1">
+                        {settingsWarnings.map((warning) => (
+                            <p key={warning} className="text-xs text-amber-400/80 break-words">
+                                {warning}
+                            </p>
+                        ))}
+                    </div>
+                )}
+
                 {/* App Update Section - Premium Design */}
                 <div className="space-y-4">
                     <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4 flex items-center gap-2">
@@ -215,6 +385,15 @@ function SettingsView({
                         onRetryCheck={checkForUpdates}
                         onInstall={handleInstallUpdate}
                         appVersion={appVersion}
+                        downloadProgress={formatDownloadProgress(appUpdateProgress)}
+                    />
+
+                    <ToggleItem
+                        icon={<Sparkles className="w-5 h-5" />}
+                        title="Beta Updates"
+                        description="Also offer pre-release versions of Godspeed when checking for updates"
+                        checked={betaUpdates}
+                        onChange={onToggleBetaUpdates}
                     />
                 </div>
 
@@ -267,8 +446,83 @@ function SettingsView({
                         onClick={handleEngineUpdate}
                         status={engineUpdateStatus}
                         statusMessage={engineUpdateMessage}
+                        progressMessage={formatDownloadProgress(engineProgress)}
+                        disabledReason={engineUpdatesDisabledReason}
+                    />
+
+                    {/* Engine Binaries Update Button */}
+                    <ActionButton
+                        icon={<RefreshCw className="w-5 h-5" />}
+                        title="Update Engine Binaries"
+                        description="Download only the engine binaries that have a newer version"
+                        onClick={handleEngineBinariesUpdate}
+                        status={engineBinariesStatus}
+                        statusMessage={engineBinariesMessage}
+                        progressMessage={formatDownloadProgress(engineProgress)}
+                        disabledReason={engineUpdatesDisabledReason}
+                    />
+
+                    <ToggleItem
+                        icon={<Sparkles className="w-5 h-5" />}
+                        title="yt-dlp Nightly Builds"
+                        description="Update yt-dlp from the nightly channel for the latest site fixes"
+                        checked={ytDlpNightly}
+                        onChange={onToggleYtDlpNightly}
+                    />
+
+                    {/* Engine Rollback Button */}
+                    <ActionButton
+                        icon={<RotateCcw className="w-5 h-5" />}
+                        title="Roll Back Engine"
+                        description="Restore the engine binaries replaced by the last update"
+                        onClick={handleEngineRollback}
+                        status={engineRollbackStatus}
+                        statusMessage={engineRollbackMessage}
                     />
                 </div>
+
+                {/* Divider */}
+                <div className="border-t border-white/10 my-6" />
+
+                {/* Update Sources Section */}
+                {updateSources && (
+                    <UpdateSourcesForm
+                        sources={updateSources}
+                        onSave={onSaveUpdateSources}
+                    />
+                )}
+
+                {/* Divider */}
+                <div className="border-t border-white/10 my-6" />
+
+                {/* Filename Template Section */}
+                {filenameTemplate !== null && (
+                    <FilenameTemplateForm
+                        template={filenameTemplate}
+                        outputPath={outputPath}
+                        onSave={onSaveFilenameTemplate}
+                    />
+                )}
+
+                {/* Divider */}
+                <div className="border-t border-white/10 my-6" />
+
+                {/* Allowed Sites Section */}
+                {allowedSites && (
+                    <AllowedSitesForm sites={allowedSites} onSave={onSaveAllowedSites} />
+                )}
+
+                {/* Divider */}
+                <div className="border-t border-white/10 my-6" />
+
+                {/* Auth Profiles Section */}
+                <AuthProfiles />
+
+                {/* Divider */}
+                <div className="border-t border-white/10 my-6" />
+
+                {/* Network Section */}
+                {network && <NetworkForm network={network} onSave={onSaveNetwork} />}
             </div>
 
             {/* Footer */}
@@ -294,6 +548,7 @@ interface AppUpdateCardProps {
     onRetryCheck: () => void;
     onInstall: () => void;
     appVersion: string;
+    downloadProgress: string;
 }
 
 function AppUpdateCard({
@@ -305,6 +560,7 @@ function AppUpdateCard({
     onRetryCheck,
     onInstall,
     appVersion,
+    downloadProgress,
 }: AppUpdateCardProps) {
     // Determine current display state
     const isChecking = status === "checking";
@@ -358,7 +614,7 @@ function AppUpdateCard({
                             {isDownloading && "Downloading Update..."}
                             {isInstalling && "Installing Update..."}
                             {isUpToDate && "You're Up to Date!"}
-                            {hasUpdate && `Version ${updateInfo?.latest_version} Available!`}
+                            {hasUpdate && `Version ${updateInfo?.update_version} Available!`}
                             {hasCheckError && "Update Check Failed"}
                             {hasInstallError && "Installation Failed"}
                             {status === "idle" && !isDownloading && !isInstalling && !hasInstallError && "Check for Updates"}
@@ -367,14 +623,24 @@ function AppUpdateCard({
                         {/* Description */}
                         <p className={`text-sm mt-1 ${hasCheckError || hasInstallError ? "text-amber-400/80" : "text-white/50"
                             }`}>
-                            {isChecking && "Connecting to GitHub..."}
-                            {isDownloading && "Please wait while the installer downloads..."}
+                            {isChecking && "Connecting to update server..."}
+                            {isDownloading && (downloadProgress || "Please wait while the installer downloads...")}
                             {isInstalling && "The installer will start shortly. This app will close."}
-                            {isUpToDate && (
+                            {isUpToDate && (!updateInfo?.latest_version || updateInfo.latest_version === appVersion) && (
                                 <>Running version <span className="font-mono text-[#00ff88]">v{appVersion}</span> — the latest release</>
                             )}
+                            {isUpToDate && updateInfo?.latest_version && updateInfo.latest_version !== appVersion && (
+                                <>Running version <span className="font-mono text-[#00ff88]">v{appVersion}</span> — latest release: <span className="font-mono">v{updateInfo.latest_version}</span></>
+                            )}
                             {hasUpdate && (
-                                <>Current: <span className="font-mono">v{appVersion}</span> → New: <span className="font-mono text-[#00ff88]">v{updateInfo?.latest_version}</span></>
+                                <>Current: <span className="font-mono">v{appVersion}</span> → New: <span className="font-mono text-[#00ff88]">v{updateInfo?.update_version}</span>
+                                    {updateInfo?.prerelease && <span className="ml-2 text-amber-400">(beta)</span>}
+                                    {updateInfo?.published_at && (
+                                        <span className="ml-2 text-white/30">
+                                            released {new Date(updateInfo.published_at).toLocaleDateString()}
+                                        </span>
+                                    )}
+                                </>
                             )}
                             {hasCheckError && updateError}
                             {hasInstallError && installError}
@@ -382,6 +648,13 @@ function AppUpdateCard({
                                 "Click to check for new versions"
                             )}
                         </p>
+
+                        {/* Release Notes */}
+                        {hasUpdate && updateInfo?.release_notes && (
+                            <pre className="mt-3 max-h-40 overflow-y-auto whitespace-pre-wrap font-sans text-xs text-white/40">
+                                {updateInfo.release_notes}
+                            </pre>
+                        )}
                     </div>
                 </div>
 
@@ -503,6 +776,270 @@ function ToggleItem({ icon, title, description, checked, onChange }: ToggleItemP
     );
 }
 
+interface UpdateSourcesFormProps {
+    sources: UpdateSources;
+    onSave: (sources: UpdateSources) => void;
+}
+
+/**
+ * Editable app/engine update endpoints, for mirrored releases.
+ * Each field is saved when it loses focus.
+ */
+function UpdateSourcesForm({ sources, onSave }: UpdateSourcesFormProps) {
+    const [draft, setDraft] = useState<UpdateSources>(sources);
+
+    useEffect(() => {
+        setDraft(sources);
+    }, [sources]);
+
+    const save = (next: UpdateSources) => {
+        if (JSON.stringify(next) !== JSON.stringify(sources)) {
+            onSave(next);
+        }
+    };
+
+    const inputClass =
+        "w-full bg-black/40 border border-white/10 rounded-lg px-3 py-2 text-sm text-white font-mono placeholder-white/30 focus:outline-none focus:border-[#00ff88]/50 transition-all";
+
+    const urlField = (label: string, key: "app_url" | "engine_package_url" | "engine_index_url") => (
+        <label className="block">
+            <span className="text-white/40 text-xs">{label}</span>
+            <input
+                type="text"
+                value={draft[key]}
+                onChange={(e) => setDraft({ ...draft, [key]: e.target.value })}
+                onBlur={() => save(draft)}
+                className={inputClass}
+            />
+        </label>
+    );
+
+    return (
+        <div className="space-y-4">
+            <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4">
+                Update Sources
+            </h2>
+
+            <label className="block">
+                <span className="text-white/40 text-xs">App update feed format</span>
+                <select
+                    value={draft.app_feed}
+                    onChange={(e) => {
+                        const next = { ...draft, app_feed: e.target.value as UpdateSources["app_feed"] };
+                        setDraft(next);
+                        save(next);
+                    }}
+                    className={inputClass}
+                >
+                    <option value="github_releases">GitHub Releases API</option>
+                    <option value="manifest">Static JSON manifest</option>
+                </select>
+            </label>
+
+            {urlField("App update feed URL", "app_url")}
+            {urlField("Engine package URL", "engine_package_url")}
+            {urlField("Engine index URL", "engine_index_url")}
+        </div>
+    );
+}
+
+interface NetworkFormProps {
+    network: NetworkConfig;
+    onSave: (network: NetworkConfig) => void;
+}
+
+/**
+ * Proxy, bandwidth limit, source address and CA bundle for downloads and updates.
+ * Each field is saved when it loses focus.
+ */
+function NetworkForm({ network, onSave }: NetworkFormProps) {
+    const [draft, setDraft] = useState<NetworkConfig>(network);
+
+    useEffect(() => {
+        setDraft(network);
+    }, [network]);
+
+    const save = () => {
+        if (JSON.stringify(draft) !== JSON.stringify(network)) {
+            onSave(draft);
+        }
+    };
+
+    const field = (label: string, key: keyof NetworkConfig, placeholder: string) => (
+        <label className="block">
+            <span className="text-white/40 text-xs">{label}</span>
+            <input
+                type="text"
+                value={draft[key]}
+                placeholder={placeholder}
+                onChange={(e) => setDraft({ ...draft, [key]: e.target.value })}
+                onBlur={save}
+                className="w-full bg-black/40 border border-white/10 rounded-lg px-3 py-2 text-sm text-white font-mono placeholder-white/30 focus:outline-none focus:border-[#00ff88]/50 transition-all"
+            />
+        </label>
+    );
+
+    return (
+        <div className="space-y-4">
+            <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4">
+                Network
+            </h2>
+
+            {field("Proxy", "proxy", "http://proxy:8080 or socks5://proxy:1080")}
+            <p className="text-xs text-white/30">
+                A password entered as user:password@ is stored encrypted and hidden here; it is kept while the user stays the same.
+            </p>
+            {field("Download speed limit (bytes/s)", "limit_rate", "e.g. 500K or 2M")}
+            {field("Source IP address", "source_address", "Default")}
+            {field("CA certificate bundle (PEM)", "ca_bundle", "Built-in certificates")}
+        </div>
+    );
+}
+
+interface FilenameTemplateFormProps {
+    template: string;
+    outputPath: string;
+    onSave: (template: string) => void;
+}
+
+/**
+ * Filename template for new downloads, saved when the field loses focus.
+ * A sample URL shows the resulting path without downloading anything.
+ */
+function FilenameTemplateForm({ template, outputPath, onSave }: FilenameTemplateFormProps) {
+    const [draft, setDraft] = useState(template);
+    const [sampleUrl, setSampleUrl] = useState("");
+    const [preview, setPreview] = useState("");
+    const [previewError, setPreviewError] = useState("");
+    const [isPreviewing, setIsPreviewing] = useState(false);
+
+    useEffect(() => {
+        setDraft(template);
+    }, [template]);
+
+    const save = () => {
+        if (draft.trim() && draft !== template) {
+            onSave(draft);
+        }
+    };
+
+    const showPreview = async () => {
+        setIsPreviewing(true);
+        setPreview("");
+        setPreviewError("");
+
+        try {
+            const path = await invoke<string>("preview_output_template", {
+                url: sampleUrl.trim(),
+                outputPath,
+                template: draft,
+            });
+            setPreview(path);
+        } catch (error) {
+            const message =
+                typeof error === "object" && error !== null && "message" in error
+                    ? String((error as { message: unknown }).message)
+                    : String(error);
+            setPreviewError(message);
+        } finally {
+            setIsPreviewing(false);
+        }
+    };
+
+    const inputClass =
+        "w-full bg-black/40 border border-white/10 rounded-lg px-3 py-2 text-sm text-white font-mono placeholder-white/30 focus:outline-none focus:border-[#00ff88]/50 transition-all";
+
+    return (
+        <div className="space-y-4">
+            <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4">
+                Filename Template
+            </h2>
+
+            <label className="block">
+                <span className="text-white/40 text-xs">
+                    yt-dlp output template without extension, e.g. %(uploader)s/%(title)s
+                </span>
+                <input
+                    type="text"
+                    value={draft}
+                    onChange={(e) => setDraft(e.target.value)}
+                    onBlur={save}
+                    className={inputClass}
+                />
+            </label>
+
+            <div className="flex gap-2">
+                <input
+                    type="text"
+                    value={sampleUrl}
+                    placeholder="Sample URL to preview the filename"
+                    onChange={(e) => setSampleUrl(e.target.value)}
+                    className={inputClass}
+                />
+                <button
+                    onClick={showPreview}
+                    disabled={isPreviewing || !sampleUrl.trim() || !draft.trim()}
+                    className="shrink-0 px-4 py-2 rounded-lg font-medium text-sm bg-white/5 text-white/70 hover:bg-white/10 hover:text-white disabled:text-white/30 disabled:cursor-not-allowed transition-all duration-200"
+                >
+                    {isPreviewing ? <Loader2 className="w-4 h-4 animate-spin" /> : "Preview"}
+                </button>
+            </div>
+
+            {preview && <p className="text-xs text-white/60 font-mono break-all">{preview}</p>}
+            {previewError && <p className="text-xs text-amber-400/80 break-words">{previewError}</p>}
+        </div>
+    );
+}
+
+interface AllowedSitesFormProps {
+    sites: string[];
+    onSave: (sites: string[]) => void;
+}
+
+/**
+ * Site allowlist for downloads, one host name per line.
+ * An empty list allows every site. Saved when the field loses focus.
+ */
+function AllowedSitesForm({ sites, onSave }: AllowedSitesFormProps) {
+    const [draft, setDraft] = useState(sites.join("\n"));
+
+    useEffect(() => {
+        setDraft(sites.join("\n"));
+    }, [sites]);
+
+    const save = () => {
+        const next = draft
+            .split(/[\s,]+/)
+            .map((site) => site.trim())
+            .filter((site) => site.length > 0);
+
+        if (JSON.stringify(next) !== JSON.stringify(sites)) {
+            onSave(next);
+        }
+    };
+
+    return (
+        <div className="space-y-4">
+            <h2 className="text-xs font-medium text-white/50 tracking-wider uppercase mb-4">
+                Allowed Sites
+            </h2>
+
+            <label className="block">
+                <span className="text-white/40 text-xs">
+                    Only download from these sites (one per line, e.g. youtube.com). Leave empty to allow all.
+                </span>
+                <textarea
+                    value={draft}
+                    rows={3}
+                    onChange={(e) => setDraft(e.target.value)}
+                    onBlur={save}
+                    className="w-full bg-black/40 border border-white/10 rounded-lg px-3 py-2 text-sm text-white font-mono placeholder-white/30 focus:outline-none focus:border-[#00ff88]/50 transition-all"
+                />
+            </label>
+        </div>
+    );
+}
+
 interface ActionButtonProps {
     icon: React.ReactNode;
     title: string;
@@ -510,6 +1047,9 @@ interface ActionButtonProps {
     onClick: () => void;
     status: UpdateStatus;
     statusMessage?: string;
+    progressMessage?: string;
+    /** Reason the action is unavailable; disables the button when set */
+    disabledReason?: string;
 }
 
 function ActionButton({
@@ -519,8 +1059,11 @@ function ActionButton({
     onClick,
     status,
     statusMessage,
+    progressMessage,
+    disabledReason,
 }: ActionButtonProps) {
     const isLoading = status === "loading";
+    const isDisabled = isLoading || disabledReason !== undefined;
     const isSuccess = status === "success";
     const isError = status === "error";
 
@@ -544,12 +1087,12 @@ function ActionButton({
                         <h3 className="text-white font-medium">{title}</h3>
                         <p className={`text-sm mt-0.5 ${isError ? "text-amber-400/80" : "text-white/40"}`}>
                             {isLoading
-                                ? "Downloading engine files..."
+                                ? progressMessage || "Downloading engine files..."
                                 : isSuccess
                                     ? statusMessage || "Update complete!"
                                     : isError
                                         ? statusMessage || "Update failed"
-                                        : description}
+                                        : disabledReason ?? description}
                         </p>
                     </div>
                 </div>
@@ -557,8 +1100,8 @@ function ActionButton({
                 {/* Right: Action Button */}
                 <button
                     onClick={onClick}
-                    disabled={isLoading}
-                    className={`shrink-0 px-4 py-2 rounded-lg font-medium text-sm transition-all duration-200 flex items-center gap-2 ${isLoading
+                    disabled={isDisabled}
+                    className={`shrink-0 px-4 py-2 rounded-lg font-medium text-sm transition-all duration-200 flex items-center gap-2 ${isDisabled
                         ? "bg-white/5 text-white/30 cursor-not-allowed"
                         : isSuccess
                             ? "bg-[#00ff88]/20 text-[#00ff88] hover:bg-[#00ff88]/30"